@@ -0,0 +1,76 @@
+pub mod argumentos;
+pub mod comandos;
+pub mod error;
+use error::ErrorCli;
+
+/// Texto de ayuda general de la línea de comandos.
+pub const AYUDA: &str = "\
+Uso: tp_individual <subcomando> [opciones]
+     tp_individual maze.txt /path/to/output_dir/ x y
+
+Subcomandos:
+  detonate   Detona una bomba y guarda o muestra el tablero resultante
+  validate   Verifica que un archivo de tablero sea válido
+  render     Dibuja un tablero en la terminal
+  convert    Convierte un tablero a otro formato
+  stats      Muestra estadísticas de un tablero
+  diff       Compara dos tableros celda por celda
+  replay     Reproduce una sesión grabada y verifica el tablero final
+  transform  Rota, refleja, recorta o agranda un tablero
+  generate   Genera un tablero resoluble al azar
+
+Use 'tp_individual <subcomando> --help' para ver las opciones de cada subcomando.
+
+Códigos de salida:
+  0  Éxito
+  2  Argumentos incorrectos
+  3  Archivo de tablero inválido
+  4  Error de lectura o escritura
+  5  No hay una bomba en las coordenadas indicadas
+  6  El tablero final de una repetición no coincide con el checksum guardado
+
+Los errores se informan por la salida de errores. En el modo 'maze.txt output_dir x y' también
+se escriben en el archivo de salida; con 'detonate' se pide con --error-file.
+";
+
+/// Indica si `nombre` es un subcomando conocido.
+pub fn es_subcomando(nombre: &str) -> bool {
+    comandos::SUBCOMANDOS.contains(&nombre)
+}
+
+/// Ejecuta el subcomando `nombre` con los argumentos que lo siguen.
+pub fn ejecutar(nombre: &str, args: &[String]) -> Result<(), ErrorCli> {
+    match nombre {
+        "detonate" => comandos::detonate(args),
+        "validate" => comandos::validate(args),
+        "render" => comandos::render(args),
+        "convert" => comandos::convert(args),
+        "stats" => comandos::stats(args),
+        "diff" => comandos::diff(args),
+        "replay" => comandos::replay(args),
+        "transform" => comandos::transform(args),
+        "generate" => comandos::generate(args),
+        _ => Err(ErrorCli::Uso(format!(
+            "ERROR: Subcomando desconocido: {}.\n{}",
+            nombre, AYUDA
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_todos_los_subcomandos_se_ejecutan() {
+        let ayuda = vec!["--help".to_string()];
+        for subcomando in comandos::SUBCOMANDOS {
+            assert!(es_subcomando(subcomando));
+            assert_eq!(ejecutar(subcomando, &ayuda), Ok(()), "{}", subcomando);
+        }
+        assert!(matches!(
+            ejecutar("explotar", &ayuda),
+            Err(ErrorCli::Uso(_))
+        ));
+    }
+}
@@ -0,0 +1,148 @@
+use super::error::ErrorCli;
+use std::collections::{HashMap, HashSet};
+
+/// Representa los argumentos de un subcomando separados en posicionales, opciones con valor
+/// (`--nombre valor`) y banderas (`--nombre`).
+#[derive(Debug, Default)]
+pub struct Argumentos {
+    pub posicionales: Vec<String>,
+    opciones: HashMap<String, String>,
+    banderas: HashSet<String>,
+}
+
+impl Argumentos {
+    /// Separa los argumentos según las opciones y banderas que acepta el subcomando.
+    ///
+    /// # Argumentos
+    ///
+    /// * `args`: Los argumentos que siguen al nombre del subcomando.
+    /// * `con_valor`: Las opciones que requieren un valor.
+    /// * `banderas`: Las opciones que no llevan valor.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve los argumentos separados, o `ErrorCli::Uso` si aparece una opción desconocida,
+    /// una opción sin valor o una opción repetida.
+    pub fn parsear(
+        args: &[String],
+        con_valor: &[&str],
+        banderas: &[&str],
+    ) -> Result<Argumentos, ErrorCli> {
+        let mut argumentos = Argumentos::default();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            if !arg.starts_with("--") {
+                argumentos.posicionales.push(arg.clone());
+            } else if banderas.contains(&arg.as_str()) {
+                argumentos.banderas.insert(arg.clone());
+            } else if con_valor.contains(&arg.as_str()) {
+                let valor = iter.next().ok_or_else(|| {
+                    ErrorCli::Uso(format!("ERROR: La opción {} requiere un valor.", arg))
+                })?;
+                if argumentos
+                    .opciones
+                    .insert(arg.clone(), valor.clone())
+                    .is_some()
+                {
+                    return Err(ErrorCli::Uso(format!(
+                        "ERROR: La opción {} está repetida.",
+                        arg
+                    )));
+                }
+            } else {
+                return Err(ErrorCli::Uso(format!(
+                    "ERROR: Opción desconocida: {}.",
+                    arg
+                )));
+            }
+        }
+        Ok(argumentos)
+    }
+
+    /// Devuelve el valor de la opción `nombre`, si fue indicada.
+    pub fn opcion(&self, nombre: &str) -> Option<&str> {
+        self.opciones.get(nombre).map(String::as_str)
+    }
+
+    /// Devuelve el valor de la opción `nombre`, o un error de uso si no fue indicada.
+    pub fn opcion_requerida(&self, nombre: &str) -> Result<&str, ErrorCli> {
+        self.opcion(nombre)
+            .ok_or_else(|| ErrorCli::Uso(format!("ERROR: Falta la opción {}.", nombre)))
+    }
+
+    /// Indica si la bandera `nombre` fue indicada.
+    pub fn bandera(&self, nombre: &str) -> bool {
+        self.banderas.contains(nombre)
+    }
+
+    /// Devuelve el único argumento posicional, o un error de uso si hay más o menos de uno.
+    pub fn posicional_unico(&self, descripcion: &str) -> Result<&str, ErrorCli> {
+        match self.posicionales.as_slice() {
+            [valor] => Ok(valor),
+            [] => Err(ErrorCli::Uso(format!("ERROR: Falta {}.", descripcion))),
+            _ => Err(ErrorCli::Uso(format!(
+                "ERROR: Se esperaba un único argumento: {}.",
+                descripcion
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(valores: &[&str]) -> Vec<String> {
+        valores.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parsear() {
+        let resultado = Argumentos::parsear(
+            &args(&["maze.txt", "--x", "1", "--show", "--y", "2"]),
+            &["--x", "--y", "--out"],
+            &["--show"],
+        );
+        let Ok(argumentos) = resultado else {
+            panic!("Se esperaba poder parsear los argumentos");
+        };
+
+        assert_eq!(argumentos.posicional_unico("el tablero"), Ok("maze.txt"));
+        assert_eq!(argumentos.opcion("--x"), Some("1"));
+        assert_eq!(argumentos.opcion_requerida("--y"), Ok("2"));
+        assert_eq!(argumentos.opcion("--out"), None);
+        assert!(argumentos.opcion_requerida("--out").is_err());
+        assert!(argumentos.bandera("--show"));
+    }
+
+    #[test]
+    fn test_parsear_errores() {
+        let sin_valor = Argumentos::parsear(&args(&["--x"]), &["--x"], &[]);
+        assert_eq!(
+            sin_valor.map(|_| ()),
+            Err(ErrorCli::Uso(
+                "ERROR: La opción --x requiere un valor.".to_string()
+            ))
+        );
+
+        let desconocida = Argumentos::parsear(&args(&["--z"]), &["--x"], &[]);
+        assert_eq!(
+            desconocida.map(|_| ()),
+            Err(ErrorCli::Uso("ERROR: Opción desconocida: --z.".to_string()))
+        );
+
+        let repetida = Argumentos::parsear(&args(&["--x", "1", "--x", "2"]), &["--x"], &[]);
+        assert!(repetida.is_err());
+    }
+
+    #[test]
+    fn test_posicional_unico() {
+        let vacio = Argumentos::default();
+        assert!(vacio.posicional_unico("el tablero").is_err());
+
+        let Ok(dos) = Argumentos::parsear(&args(&["a.txt", "b.txt"]), &[], &[]) else {
+            panic!("Se esperaba poder parsear los argumentos");
+        };
+        assert!(dos.posicional_unico("el tablero").is_err());
+    }
+}
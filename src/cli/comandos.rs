@@ -0,0 +1,934 @@
+use super::argumentos::Argumentos;
+use super::error::ErrorCli;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+use tp_individual::tablero::animacion::{reproducir, Ritmo};
+use tp_individual::tablero::diferencias::{diferencias_a_csv, diferencias_a_texto};
+use tp_individual::tablero::generacion::{generar, Generacion};
+use tp_individual::tablero::model::objeto::Objeto;
+use tp_individual::tablero::renderizado::salida_admite_color;
+use tp_individual::tablero::repeticion::{ErrorDeRepeticion, Instruccion, Repeticion};
+use tp_individual::tablero::transformacion::Transformacion;
+use tp_individual::tablero::validacion::{validar_archivo, Gravedad};
+use tp_individual::tablero::{crear_tablero, escribir_tablero, guardar_tablero, Tablero};
+
+/// Nombres de los subcomandos disponibles.
+pub const SUBCOMANDOS: [&str; 9] = [
+    "detonate",
+    "validate",
+    "render",
+    "convert",
+    "stats",
+    "diff",
+    "replay",
+    "transform",
+    "generate",
+];
+
+const AYUDA_DETONATE: &str = "\
+Uso: tp_individual detonate <maze> --x <x> --y <y> [opciones]
+
+Detona la bomba ubicada en (x, y) y escribe el tablero resultante.
+
+Opciones:
+  --x <x>                Columna de la bomba a detonar
+  --y <y>                Fila de la bomba a detonar
+  --out <dir>            Guarda el resultado en <dir> con el nombre del archivo de entrada
+                         (si se omite, lo escribe en la salida estándar)
+  --dot <archivo>        Exporta el grafo de dependencias entre bombas en formato DOT
+  --error-file           Si algo falla, escribe también el error en el archivo de salida
+                         dentro de <dir> (requiere --out)
+  --show                 Dibuja el tablero resultante resaltando la explosión
+  --animate <ms|manual>  Muestra la explosión paso a paso
+  --record <archivo>     Graba la sesión en una repetición que se puede verificar con 'replay'
+";
+
+const AYUDA_VALIDATE: &str = "\
+Uso: tp_individual validate <maze> [--strict]
+
+Revisa el archivo de tablero sin detonar nada e informa todos los problemas encontrados:
+símbolos inválidos, filas irregulares, bombas con alcance mayor al tablero, desvíos que apuntan
+a una pared o fuera del tablero, ciclos de desvíos y enemigos que no se pueden eliminar.
+
+Termina con error si hay algún error, o si hay advertencias y se indicó --strict.
+";
+
+const AYUDA_RENDER: &str = "\
+Uso: tp_individual render <maze> [--color | --no-color]
+
+Dibuja el tablero en la terminal. Por defecto usa colores solo si la salida es una terminal.
+";
+
+const AYUDA_CONVERT: &str = "\
+Uso: tp_individual convert <maze> --to <formato> [--out <archivo>]
+
+Convierte el tablero a otro formato y lo escribe en <archivo> o en la salida estándar.
+
+Formatos:
+  txt      El formato de los archivos de tablero
+  dot      Grafo de dependencias entre bombas (Graphviz)
+  heatmap  Cantidad de bombas que alcanzan cada celda
+  csv      Mapa de calor en formato CSV
+";
+
+const AYUDA_STATS: &str = "\
+Uso: tp_individual stats <maze> [--x <x> --y <y>] [--solve] [--json]
+
+Muestra estadísticas del tablero: cantidad de cada tipo de objeto, vida total de los enemigos,
+distribución de alcances de las bombas, desvíos por dirección y, si se pide, datos de resolución.
+
+Opciones:
+  --x <x>  Columna de una bomba a detonar para comparar el tablero antes y después
+  --y <y>  Fila de la bomba a detonar (requerida junto con --x)
+  --solve  Busca si el tablero es resoluble y con cuántas detonaciones manuales; en tableros
+           con muchas bombas la búsqueda puede quedar sin decidir
+  --json   Escribe el informe en formato JSON
+";
+
+const AYUDA_DIFF: &str = "\
+Uso: tp_individual diff <antes> <despues> [--format <formato>] [--color | --no-color]
+
+Compara dos archivos de tablero celda por celda e informa los cambios, por ejemplo
+'(2,3): B2 -> _' o '(1,1): F2 -> F1'.
+
+Formatos:
+  text  Un cambio por línea (por defecto)
+  grid  Dibuja el segundo tablero resaltando las celdas que cambiaron, seguido de los cambios
+  csv   Columnas x,y,antes,despues
+";
+
+const AYUDA_REPLAY: &str = "\
+Uso: tp_individual replay <archivo> [--out <archivo>] [--show]
+
+Reproduce una sesión grabada y verifica que el tablero final tenga el checksum guardado.
+
+Formato de la repetición (una instrucción por línea, '#' inicia un comentario):
+  cargar <tablero>     Carga el tablero, con ruta relativa a la repetición (primera línea)
+  detonar <x> <y>      Detona la bomba en (x, y)
+  editar <x> <y> <s>   Reemplaza la celda (x, y) por el objeto de símbolo <s>
+  checksum <hex>       Checksum esperado del tablero final (opcional, última línea)
+
+Opciones:
+  --out <archivo>  Escribe el tablero final en <archivo>
+  --show           Dibuja el tablero final
+";
+
+const AYUDA_TRANSFORM: &str = "\
+Uso: tp_individual transform <maze> <operación>... [--out <archivo>]
+
+Aplica las operaciones en orden y escribe el tablero resultante en <archivo> o en la salida
+estándar. Los desvíos y las posiciones recordadas por los enemigos se ajustan a la nueva forma.
+
+Operaciones:
+  rotate <90|180|270>           Gira el tablero en sentido horario
+  mirror <h|v>                  Refleja izquierda-derecha (h) o arriba-abajo (v)
+  crop <x> <y> <ancho> <alto>   Conserva el cuadrado que empieza en (x, y); el ancho y el
+                                alto deben ser iguales
+  pad <n> <empty|wall>          Agrega n celdas vacías o de pared en cada borde
+
+Ejemplo: tp_individual transform maze.txt rotate 90 pad 1 wall --out nuevo.txt
+";
+
+const AYUDA_GENERATE: &str = "\
+Uso: tp_individual generate [opciones]
+
+Genera un tablero al azar que se puede resolver y lo escribe en <archivo> o en la salida
+estándar. Se prueban tableros hasta encontrar uno resoluble; si se pide una cantidad de
+detonaciones, el tablero se resuelve con exactamente esa cantidad de detonaciones manuales.
+
+Opciones:
+  --size <n>         Cantidad de filas y columnas (por defecto 7)
+  --bombs <n>        Cantidad de bombas (por defecto 3)
+  --enemies <n>      Cantidad de enemigos (por defecto 3)
+  --rocks <n>        Cantidad de rocas (por defecto 2)
+  --walls <n>        Cantidad de paredes (por defecto 2)
+  --detours <n>      Cantidad de desvíos (por defecto 1)
+  --max-range <n>    Alcance máximo de las bombas (por defecto 3)
+  --detonations <n>  Cantidad exacta de detonaciones manuales para resolverlo
+  --attempts <n>     Tableros que se prueban antes de darse por vencido (por defecto 1000)
+  --seed <n>         Semilla: la misma semilla genera el mismo tablero (por defecto 1)
+  --out <archivo>    Escribe el tablero en <archivo> en lugar de la salida estándar
+";
+
+/// Subcomando `detonate`: detona una bomba y guarda o muestra el resultado.
+pub fn detonate(args: &[String]) -> Result<(), ErrorCli> {
+    if pide_ayuda(args, AYUDA_DETONATE) {
+        return Ok(());
+    }
+    let argumentos = Argumentos::parsear(
+        args,
+        &["--x", "--y", "--out", "--dot", "--animate", "--record"],
+        &["--show", "--error-file"],
+    )?;
+    let archivo = argumentos.posicional_unico("el archivo de tablero")?;
+    let x = parse_coordenada(argumentos.opcion_requerida("--x")?, "X")?;
+    let y = parse_coordenada(argumentos.opcion_requerida("--y")?, "Y")?;
+    let ritmo = argumentos
+        .opcion("--animate")
+        .map(parse_ritmo)
+        .transpose()?;
+    let directorio_de_error = match (
+        argumentos.bandera("--error-file"),
+        argumentos.opcion("--out"),
+    ) {
+        (true, None) => {
+            return Err(ErrorCli::Uso(
+                "ERROR: La opción --error-file requiere --out.".to_string(),
+            ))
+        }
+        (true, Some(dir)) => Some(dir),
+        (false, _) => None,
+    };
+
+    let resultado = detonar(&argumentos, archivo, (x, y), ritmo);
+    match directorio_de_error {
+        Some(dir) => resultado.map_err(|error| error.guardar_en_archivo(dir, archivo)),
+        None => resultado,
+    }
+}
+
+fn detonar(
+    argumentos: &Argumentos,
+    archivo: &str,
+    (x, y): (i32, i32),
+    ritmo: Option<Ritmo>,
+) -> Result<(), ErrorCli> {
+    let mostrar = argumentos.bandera("--show");
+    let mut tablero = cargar(archivo)?;
+    if let Some(archivo_dot) = argumentos.opcion("--dot") {
+        escribir_salida(Some(archivo_dot), &tablero.grafo_de_bombas().a_dot())?;
+    }
+    if let Some(ritmo) = ritmo {
+        animar(&tablero, x, y, &ritmo)?;
+    }
+
+    tablero.detonar(x, y).map_err(ErrorCli::Detonacion)?;
+    if let Some(archivo_repeticion) = argumentos.opcion("--record") {
+        grabar(&tablero, archivo, (x, y), archivo_repeticion)?;
+    }
+    if mostrar {
+        print!("{}", tablero.renderizar(salida_admite_color()));
+    }
+
+    match argumentos.opcion("--out") {
+        Some(dir) => guardar_tablero(dir, &tablero, archivo)
+            .map_err(|e| ErrorCli::al_escribir("No se pudo guardar el tablero", e)),
+        None if !mostrar => escribir_tablero(&mut io::stdout(), &tablero)
+            .map_err(|e| ErrorCli::al_escribir("No se pudo escribir el tablero", e)),
+        None => Ok(()),
+    }
+}
+
+/// Subcomando `validate`: informa todos los problemas del archivo de tablero.
+pub fn validate(args: &[String]) -> Result<(), ErrorCli> {
+    if pide_ayuda(args, AYUDA_VALIDATE) {
+        return Ok(());
+    }
+    let argumentos = Argumentos::parsear(args, &[], &["--strict"])?;
+    let archivo = argumentos.posicional_unico("el archivo de tablero")?;
+    let problemas = validar_archivo(archivo).map_err(ErrorCli::al_cargar)?;
+    for problema in &problemas {
+        println!("{}", problema);
+    }
+
+    let errores = problemas
+        .iter()
+        .filter(|p| p.gravedad == Gravedad::Error)
+        .count();
+    let advertencias = problemas.len() - errores;
+    let resumen = format!(
+        "{}: {} errores, {} advertencias.",
+        archivo, errores, advertencias
+    );
+    if errores > 0 || (advertencias > 0 && argumentos.bandera("--strict")) {
+        return Err(ErrorCli::TableroInvalido(format!("ERROR: {}", resumen)));
+    }
+    println!("OK: {}", resumen);
+    Ok(())
+}
+
+/// Subcomando `render`: dibuja el tablero en la terminal.
+pub fn render(args: &[String]) -> Result<(), ErrorCli> {
+    if pide_ayuda(args, AYUDA_RENDER) {
+        return Ok(());
+    }
+    let argumentos = Argumentos::parsear(args, &[], &["--color", "--no-color"])?;
+    let archivo = argumentos.posicional_unico("el archivo de tablero")?;
+    let color = elegir_color(&argumentos)?;
+    let tablero = cargar(archivo)?;
+    print!("{}", tablero.renderizar(color));
+    Ok(())
+}
+
+/// Subcomando `convert`: convierte el tablero a otro formato.
+pub fn convert(args: &[String]) -> Result<(), ErrorCli> {
+    if pide_ayuda(args, AYUDA_CONVERT) {
+        return Ok(());
+    }
+    let argumentos = Argumentos::parsear(args, &["--to", "--out"], &[])?;
+    let archivo = argumentos.posicional_unico("el archivo de tablero")?;
+    let formato = argumentos.opcion_requerida("--to")?;
+    let tablero = cargar(archivo)?;
+    let contenido = convertir(&tablero, formato)?;
+    escribir_salida(argumentos.opcion("--out"), &contenido)
+}
+
+/// Subcomando `stats`: muestra estadísticas del tablero.
+pub fn stats(args: &[String]) -> Result<(), ErrorCli> {
+    if pide_ayuda(args, AYUDA_STATS) {
+        return Ok(());
+    }
+    let argumentos = Argumentos::parsear(args, &["--x", "--y"], &["--solve", "--json"])?;
+    let archivo = argumentos.posicional_unico("el archivo de tablero")?;
+    let detonacion = match (argumentos.opcion("--x"), argumentos.opcion("--y")) {
+        (Some(x), Some(y)) => Some((parse_coordenada(x, "X")?, parse_coordenada(y, "Y")?)),
+        (None, None) => None,
+        _ => {
+            return Err(ErrorCli::Uso(
+                "ERROR: Las opciones --x y --y se deben indicar juntas.".to_string(),
+            ))
+        }
+    };
+    let tablero = cargar(archivo)?;
+    print!(
+        "{}",
+        informe(
+            &tablero,
+            detonacion,
+            argumentos.bandera("--solve"),
+            argumentos.bandera("--json")
+        )?
+    );
+    Ok(())
+}
+
+/// Subcomando `diff`: compara dos archivos de tablero celda por celda.
+pub fn diff(args: &[String]) -> Result<(), ErrorCli> {
+    if pide_ayuda(args, AYUDA_DIFF) {
+        return Ok(());
+    }
+    let argumentos = Argumentos::parsear(args, &["--format"], &["--color", "--no-color"])?;
+    let [archivo_antes, archivo_despues] = argumentos.posicionales.as_slice() else {
+        return Err(ErrorCli::Uso(
+            "ERROR: Se esperaban dos archivos de tablero.".to_string(),
+        ));
+    };
+    let formato = argumentos.opcion("--format").unwrap_or("text");
+    let color = elegir_color(&argumentos)?;
+    let antes = cargar(archivo_antes)?;
+    let despues = cargar(archivo_despues)?;
+    print!("{}", comparar(&antes, &despues, formato, color)?);
+    Ok(())
+}
+
+/// Subcomando `replay`: reproduce una sesión grabada y verifica el tablero final.
+pub fn replay(args: &[String]) -> Result<(), ErrorCli> {
+    if pide_ayuda(args, AYUDA_REPLAY) {
+        return Ok(());
+    }
+    let argumentos = Argumentos::parsear(args, &["--out"], &["--show"])?;
+    let archivo = argumentos.posicional_unico("el archivo de repetición")?;
+    let contenido = fs::read_to_string(archivo)
+        .map_err(|e| ErrorCli::Archivo(format!("ERROR: No se pudo leer {}: {}", archivo, e)))?;
+    let repeticion = Repeticion::parsear(&contenido).map_err(ErrorCli::TableroInvalido)?;
+    let directorio = Path::new(archivo).parent().unwrap_or(Path::new(""));
+    let tablero = repeticion.reproducir(directorio).map_err(|e| match e {
+        ErrorDeRepeticion::Carga(io::ErrorKind::InvalidData, mensaje)
+        | ErrorDeRepeticion::Instruccion(mensaje) => ErrorCli::TableroInvalido(mensaje),
+        ErrorDeRepeticion::Carga(_, mensaje) => ErrorCli::Archivo(mensaje),
+        ErrorDeRepeticion::Detonacion(mensaje) => ErrorCli::Detonacion(mensaje),
+    })?;
+
+    if argumentos.bandera("--show") {
+        print!("{}", tablero.renderizar(salida_admite_color()));
+    }
+    if let Some(salida) = argumentos.opcion("--out") {
+        let mut contenido = Vec::new();
+        escribir_tablero(&mut contenido, &tablero)
+            .map_err(|e| ErrorCli::al_escribir("No se pudo escribir el tablero", e))?;
+        escribir_salida(Some(salida), &String::from_utf8_lossy(&contenido))?;
+    }
+    repeticion
+        .verificar(&tablero)
+        .map_err(ErrorCli::Verificacion)?;
+    println!("OK: checksum {:016x}", tablero.checksum());
+    Ok(())
+}
+
+/// Subcomando `transform`: rota, refleja, recorta o agranda el tablero.
+pub fn transform(args: &[String]) -> Result<(), ErrorCli> {
+    if pide_ayuda(args, AYUDA_TRANSFORM) {
+        return Ok(());
+    }
+    let argumentos = Argumentos::parsear(args, &["--out"], &[])?;
+    let Some((archivo, operaciones)) = argumentos.posicionales.split_first() else {
+        return Err(ErrorCli::Uso(
+            "ERROR: Falta el archivo de tablero.".to_string(),
+        ));
+    };
+    let transformaciones = parse_transformaciones(operaciones)?;
+    let mut tablero = cargar(archivo)?;
+    for transformacion in &transformaciones {
+        tablero = tablero.transformar(transformacion).map_err(ErrorCli::Uso)?;
+    }
+    let contenido = convertir(&tablero, "txt")?;
+    escribir_salida(argumentos.opcion("--out"), &contenido)
+}
+
+/// Subcomando `generate`: genera un tablero resoluble al azar.
+pub fn generate(args: &[String]) -> Result<(), ErrorCli> {
+    if pide_ayuda(args, AYUDA_GENERATE) {
+        return Ok(());
+    }
+    let argumentos = Argumentos::parsear(
+        args,
+        &[
+            "--size",
+            "--bombs",
+            "--enemies",
+            "--rocks",
+            "--walls",
+            "--detours",
+            "--max-range",
+            "--detonations",
+            "--attempts",
+            "--seed",
+            "--out",
+        ],
+        &[],
+    )?;
+    if let Some(sobrante) = argumentos.posicionales.first() {
+        return Err(ErrorCli::Uso(format!(
+            "ERROR: Argumento inesperado: {}.",
+            sobrante
+        )));
+    }
+    let tablero = generar(&parse_generacion(&argumentos)?)
+        .map_err(|e| ErrorCli::Uso(format!("ERROR: {}.", e)))?;
+    let contenido = convertir(&tablero, "txt")?;
+    escribir_salida(argumentos.opcion("--out"), &contenido)
+}
+
+fn pide_ayuda(args: &[String], ayuda: &str) -> bool {
+    let pide = args.iter().any(|arg| arg == "--help" || arg == "-h");
+    if pide {
+        print!("{}", ayuda);
+    }
+    pide
+}
+
+fn elegir_color(argumentos: &Argumentos) -> Result<bool, ErrorCli> {
+    match (
+        argumentos.bandera("--color"),
+        argumentos.bandera("--no-color"),
+    ) {
+        (true, true) => Err(ErrorCli::Uso(
+            "ERROR: Las opciones --color y --no-color son excluyentes.".to_string(),
+        )),
+        (true, false) => Ok(true),
+        (false, true) => Ok(false),
+        (false, false) => Ok(salida_admite_color()),
+    }
+}
+
+fn cargar(archivo: &str) -> Result<Tablero, ErrorCli> {
+    crear_tablero(archivo).map_err(ErrorCli::al_cargar)
+}
+
+fn escribir_salida(archivo: Option<&str>, contenido: &str) -> Result<(), ErrorCli> {
+    match archivo {
+        Some(archivo) => fs::write(archivo, contenido)
+            .map_err(|e| ErrorCli::al_escribir(&format!("No se pudo escribir {}", archivo), e)),
+        None => {
+            print!("{}", contenido);
+            Ok(())
+        }
+    }
+}
+
+fn parse_coordenada(valor: &str, nombre: &str) -> Result<i32, ErrorCli> {
+    valor.parse::<i32>().map_err(|_| {
+        ErrorCli::Uso(format!(
+            "ERROR: La coordenada {} debe ser un número válido.",
+            nombre
+        ))
+    })
+}
+
+fn parse_cantidad(valor: &str, opcion: &str) -> Result<usize, ErrorCli> {
+    valor.parse::<usize>().map_err(|_| {
+        ErrorCli::Uso(format!(
+            "ERROR: El valor de {} debe ser un número no negativo.",
+            opcion
+        ))
+    })
+}
+
+fn parse_generacion(argumentos: &Argumentos) -> Result<Generacion, ErrorCli> {
+    let mut generacion = Generacion::default();
+    let cantidad = |opcion: &str, actual: usize| match argumentos.opcion(opcion) {
+        Some(valor) => parse_cantidad(valor, opcion),
+        None => Ok(actual),
+    };
+    let entero = |opcion: &str, actual: i32| {
+        i32::try_from(cantidad(opcion, actual as usize)?).map_err(|_| {
+            ErrorCli::Uso(format!(
+                "ERROR: El valor de {} es demasiado grande.",
+                opcion
+            ))
+        })
+    };
+    generacion.tamaño = entero("--size", generacion.tamaño)?;
+    generacion.bombas = cantidad("--bombs", generacion.bombas)?;
+    generacion.enemigos = cantidad("--enemies", generacion.enemigos)?;
+    generacion.rocas = cantidad("--rocks", generacion.rocas)?;
+    generacion.paredes = cantidad("--walls", generacion.paredes)?;
+    generacion.desvios = cantidad("--detours", generacion.desvios)?;
+    generacion.alcance_maximo = entero("--max-range", generacion.alcance_maximo)?;
+    generacion.intentos = cantidad("--attempts", generacion.intentos)?;
+    generacion.detonaciones = argumentos
+        .opcion("--detonations")
+        .map(|valor| parse_cantidad(valor, "--detonations"))
+        .transpose()?;
+    if let Some(semilla) = argumentos.opcion("--seed") {
+        generacion.semilla = semilla.parse::<u64>().map_err(|_| {
+            ErrorCli::Uso("ERROR: El valor de --seed debe ser un número no negativo.".to_string())
+        })?;
+    }
+    Ok(generacion)
+}
+
+fn parse_transformaciones(operaciones: &[String]) -> Result<Vec<Transformacion>, ErrorCli> {
+    let error = |detalle: String| ErrorCli::Uso(format!("ERROR: {}", detalle));
+    let numero = |valor: Option<&String>, operacion: &str| {
+        valor
+            .and_then(|v| v.parse::<usize>().ok())
+            .ok_or_else(|| error(format!("Faltan valores numéricos para '{}'.", operacion)))
+    };
+
+    let mut transformaciones = Vec::new();
+    let mut restantes = operaciones.iter();
+    while let Some(operacion) = restantes.next() {
+        let transformacion = match operacion.as_str() {
+            "rotate" => Transformacion::Rotar(numero(restantes.next(), "rotate")? as u32),
+            "mirror" => match restantes.next().map(String::as_str) {
+                Some("h") => Transformacion::EspejarHorizontal,
+                Some("v") => Transformacion::EspejarVertical,
+                _ => return Err(error("'mirror' espera 'h' o 'v'.".to_string())),
+            },
+            "crop" => Transformacion::Recortar {
+                x: numero(restantes.next(), "crop")?,
+                y: numero(restantes.next(), "crop")?,
+                ancho: numero(restantes.next(), "crop")?,
+                alto: numero(restantes.next(), "crop")?,
+            },
+            "pad" => Transformacion::Rellenar {
+                margen: numero(restantes.next(), "pad")?,
+                relleno: match restantes.next().map(String::as_str) {
+                    Some("empty") => Objeto::Vacio,
+                    Some("wall") => Objeto::Pared,
+                    _ => return Err(error("'pad' espera 'empty' o 'wall'.".to_string())),
+                },
+            },
+            otra => return Err(error(format!("Operación desconocida: {}.", otra))),
+        };
+        transformaciones.push(transformacion);
+    }
+    if transformaciones.is_empty() {
+        return Err(error("Falta indicar al menos una operación.".to_string()));
+    }
+    Ok(transformaciones)
+}
+
+fn parse_ritmo(valor: &str) -> Result<Ritmo, ErrorCli> {
+    if valor == "manual" {
+        return Ok(Ritmo::Manual);
+    }
+    valor
+        .parse::<u64>()
+        .map(|ms| Ritmo::Intervalo(Duration::from_millis(ms)))
+        .map_err(|_| {
+            ErrorCli::Uso(
+                "ERROR: El ritmo de la animación debe ser 'manual' o una cantidad de milisegundos."
+                    .to_string(),
+            )
+        })
+}
+
+fn animar(tablero: &Tablero, x: i32, y: i32, ritmo: &Ritmo) -> Result<(), ErrorCli> {
+    let cuadros = tablero
+        .animar_detonacion(x, y)
+        .map_err(ErrorCli::Detonacion)?;
+    let stdin = io::stdin();
+    reproducir(
+        &cuadros,
+        &mut io::stdout(),
+        &mut stdin.lock(),
+        ritmo,
+        salida_admite_color(),
+    )
+    .map_err(|e| ErrorCli::al_escribir("No se pudo mostrar la animación", e))
+}
+
+fn convertir(tablero: &Tablero, formato: &str) -> Result<String, ErrorCli> {
+    match formato {
+        "txt" => {
+            let mut contenido = Vec::new();
+            escribir_tablero(&mut contenido, tablero)
+                .map_err(|e| ErrorCli::al_escribir("No se pudo convertir el tablero", e))?;
+            Ok(String::from_utf8_lossy(&contenido).into_owned())
+        }
+        "dot" => Ok(tablero.grafo_de_bombas().a_dot()),
+        "heatmap" => Ok(tablero.mapa_de_calor().a_grilla()),
+        "csv" => Ok(tablero.mapa_de_calor().a_csv()),
+        _ => Err(ErrorCli::Uso(format!(
+            "ERROR: Formato desconocido: {}. Use txt, dot, heatmap o csv.",
+            formato
+        ))),
+    }
+}
+
+fn grabar(
+    tablero: &Tablero,
+    archivo: &str,
+    (x, y): (i32, i32),
+    destino: &str,
+) -> Result<(), ErrorCli> {
+    let repeticion = Repeticion {
+        instrucciones: vec![
+            Instruccion::Cargar(ruta_desde_repeticion(archivo, destino)),
+            Instruccion::Detonar(x, y),
+        ],
+        checksum: Some(tablero.checksum()),
+    };
+    escribir_salida(Some(destino), &repeticion.a_texto())
+}
+
+/// Expresa la ruta del tablero relativa al directorio de la repetición, o absoluta si el
+/// tablero no está dentro de ese directorio.
+fn ruta_desde_repeticion(archivo: &str, repeticion: &str) -> String {
+    let directorio = match Path::new(repeticion).parent() {
+        Some(directorio) if !directorio.as_os_str().is_empty() => directorio,
+        _ => Path::new("."),
+    };
+    let (Ok(archivo_absoluto), Ok(directorio)) =
+        (fs::canonicalize(archivo), fs::canonicalize(directorio))
+    else {
+        return archivo.to_string();
+    };
+    match archivo_absoluto.strip_prefix(&directorio) {
+        Ok(relativa) => relativa.to_string_lossy().into_owned(),
+        Err(_) => archivo_absoluto.to_string_lossy().into_owned(),
+    }
+}
+
+fn comparar(
+    antes: &Tablero,
+    despues: &Tablero,
+    formato: &str,
+    color: bool,
+) -> Result<String, ErrorCli> {
+    let cambios = antes.diferencias(despues);
+    match formato {
+        "text" => Ok(diferencias_a_texto(&cambios)),
+        "grid" => Ok(format!(
+            "{}\n{}",
+            antes.renderizar_diferencias(despues, color),
+            diferencias_a_texto(&cambios)
+        )),
+        "csv" => Ok(diferencias_a_csv(&cambios)),
+        _ => Err(ErrorCli::Uso(format!(
+            "ERROR: Formato desconocido: {}. Use text, grid o csv.",
+            formato
+        ))),
+    }
+}
+
+fn informe(
+    tablero: &Tablero,
+    detonacion: Option<(i32, i32)>,
+    resolver: bool,
+    json: bool,
+) -> Result<String, ErrorCli> {
+    let estadisticas = |tablero: &Tablero| {
+        if resolver {
+            tablero.estadisticas_con_resolucion()
+        } else {
+            tablero.estadisticas()
+        }
+    };
+    let antes = estadisticas(tablero);
+    let Some((x, y)) = detonacion else {
+        return Ok(if json {
+            format!("{}\n", antes.a_json())
+        } else {
+            antes.a_texto()
+        });
+    };
+
+    let mut detonado = tablero.clone();
+    detonado.detonar(x, y).map_err(ErrorCli::Detonacion)?;
+    let despues = estadisticas(&detonado);
+    let diferencia = antes.diferencia(&despues);
+    if json {
+        return Ok(format!(
+            "{{\"antes\":{},\"despues\":{},\"diferencia\":{}}}\n",
+            antes.a_json(),
+            despues.a_json(),
+            diferencia.a_json()
+        ));
+    }
+    Ok(format!(
+        "Antes:\n{}\nDespués de detonar ({}, {}):\n{}\nDiferencia:\n{}",
+        antes.a_texto(),
+        x,
+        y,
+        despues.a_texto(),
+        diferencia.a_texto()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use tp_individual::tablero::model::direccion::Direcciones;
+    use tp_individual::tablero::model::enemigo::TipoDeEnemigo;
+
+    #[test]
+    fn test_parse_coordenada() {
+        assert_eq!(parse_coordenada("3", "X"), Ok(3));
+        assert_eq!(
+            parse_coordenada("tres", "Y"),
+            Err(ErrorCli::Uso(
+                "ERROR: La coordenada Y debe ser un número válido.".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_ritmo() {
+        assert_eq!(parse_ritmo("manual"), Ok(Ritmo::Manual));
+        assert_eq!(
+            parse_ritmo("250"),
+            Ok(Ritmo::Intervalo(Duration::from_millis(250)))
+        );
+        assert!(parse_ritmo("rapido").is_err());
+    }
+
+    #[test]
+    fn test_convertir() {
+        let mut tablero = Tablero::new(2);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![Objeto::Pared, Objeto::Vacio],
+        ];
+        assert_eq!(convertir(&tablero, "txt"), Ok("B1 F1 \nW _ \n".to_string()));
+        assert_eq!(convertir(&tablero, "heatmap"), Ok("1 1\n0 0\n".to_string()));
+        assert!(convertir(&tablero, "dot").is_ok_and(|dot| dot.starts_with("digraph")));
+        assert!(convertir(&tablero, "csv").is_ok_and(|csv| csv.contains("1,0,1,0:0\n")));
+        assert!(matches!(convertir(&tablero, "xml"), Err(ErrorCli::Uso(_))));
+    }
+
+    #[test]
+    fn test_informe() {
+        let mut tablero = Tablero::new(2);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![Objeto::Pared, Objeto::Vacio],
+        ];
+        let texto = informe(&tablero, None, false, false).unwrap_or_default();
+        assert!(texto.contains("Bombas: 1 (normales: 1, traspaso: 0, área: 0)\n"));
+        assert!(texto.contains("Enemigos: 1 (vida total: 1)\n"));
+        assert!(!texto.contains("Resoluble"));
+
+        let texto = informe(&tablero, None, true, false).unwrap_or_default();
+        assert!(texto.contains("Resoluble: sí\n"));
+        assert!(texto.contains("Detonaciones mínimas: 1\n"));
+
+        let json = informe(&tablero, None, false, true).unwrap_or_default();
+        assert!(json.contains("\"resoluble\":null,\"detonaciones_minimas\":null"));
+        assert!(json.starts_with("{\"filas\":2,"));
+        assert!(json.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_informe_con_detonacion() {
+        let mut tablero = Tablero::new(2);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![Objeto::Pared, Objeto::Vacio],
+        ];
+        let texto = informe(&tablero, Some((0, 0)), false, false).unwrap_or_default();
+        assert!(texto.contains("Después de detonar (0, 0):\n"));
+        assert!(texto.ends_with(
+            "Diferencia:\nEnemigos eliminados: 1\nVida quitada: 1\nBombas consumidas: 1\n"
+        ));
+
+        let json = informe(&tablero, Some((0, 0)), false, true).unwrap_or_default();
+        assert!(json.starts_with("{\"antes\":{"));
+        assert!(json.contains(
+            "\"diferencia\":{\"enemigos_eliminados\":1,\"vida_quitada\":1,\"bombas_consumidas\":1}"
+        ));
+        assert!(matches!(
+            informe(&tablero, Some((1, 1)), false, false),
+            Err(ErrorCli::Detonacion(_))
+        ));
+    }
+
+    #[test]
+    fn test_comparar() {
+        let mut antes = Tablero::new(2);
+        antes.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![Objeto::Pared, Objeto::Vacio],
+        ];
+        let mut despues = antes.clone();
+        assert!(despues.detonar(0, 0).is_ok());
+
+        assert_eq!(
+            comparar(&antes, &despues, "text", false),
+            Ok("(0,0): B1 -> _\n(1,0): F1 -> _\n".to_string())
+        );
+        assert_eq!(
+            comparar(&antes, &despues, "grid", false),
+            Ok("[_][_]\n W  _ \n\n(0,0): B1 -> _\n(1,0): F1 -> _\n".to_string())
+        );
+        assert!(comparar(&antes, &despues, "csv", false)
+            .is_ok_and(|csv| csv.starts_with("x,y,antes,despues\n")));
+        assert!(matches!(
+            comparar(&antes, &despues, "json", false),
+            Err(ErrorCli::Uso(_))
+        ));
+    }
+
+    #[test]
+    fn test_diff_sin_dos_archivos() {
+        let args = vec!["antes.txt".to_string()];
+        assert_eq!(
+            diff(&args),
+            Err(ErrorCli::Uso(
+                "ERROR: Se esperaban dos archivos de tablero.".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_grabar_y_replay() {
+        let directorio = std::env::temp_dir().join("tp_comandos_replay");
+        assert!(fs::create_dir_all(&directorio).is_ok());
+        let maze = directorio.join("maze.txt");
+        assert!(fs::write(&maze, "B1 F1\nW _\n").is_ok());
+        let maze = maze.to_string_lossy().into_owned();
+        let destino = directorio
+            .join("sesion.replay")
+            .to_string_lossy()
+            .into_owned();
+
+        let args: Vec<String> = [&maze, "--x", "0", "--y", "0", "--record", &destino]
+            .iter()
+            .map(|a| a.to_string())
+            .collect();
+        assert!(detonate(&args).is_ok());
+        let grabado = fs::read_to_string(&destino).unwrap_or_default();
+        assert!(grabado.starts_with("cargar maze.txt\ndetonar 0 0\nchecksum "));
+        assert!(replay(std::slice::from_ref(&destino)).is_ok());
+
+        let alterado = grabado.replace("detonar 0 0\n", "");
+        assert!(fs::write(&destino, alterado).is_ok());
+        assert!(matches!(
+            replay(std::slice::from_ref(&destino)),
+            Err(ErrorCli::Verificacion(_))
+        ));
+    }
+
+    #[test]
+    fn test_replay_clasifica_los_errores() {
+        let directorio = std::env::temp_dir().join("tp_comandos_replay_errores");
+        assert!(fs::create_dir_all(&directorio).is_ok());
+        assert!(fs::write(directorio.join("maze.txt"), "B1 F1\nW _\n").is_ok());
+        assert!(fs::write(directorio.join("invalido.txt"), "B1 X\nW _\n").is_ok());
+        let destino = directorio.join("sesion.replay");
+        let args = [destino.to_string_lossy().into_owned()];
+        let reproducir = |contenido: &str| {
+            assert!(fs::write(&destino, contenido).is_ok());
+            replay(&args).map_err(|e| e.codigo_salida())
+        };
+
+        assert_eq!(reproducir("cargar no_existe.txt\n"), Err(4));
+        assert_eq!(reproducir("cargar invalido.txt\n"), Err(3));
+        assert_eq!(reproducir("cargar maze.txt\neditar 5 5 R\n"), Err(3));
+        assert_eq!(reproducir("cargar maze.txt\ndetonar 1 1\n"), Err(5));
+        assert_eq!(reproducir("cargar maze.txt\ndetonar 0 0\n"), Ok(()));
+    }
+
+    #[test]
+    fn test_parse_transformaciones() {
+        let operaciones: Vec<String> = ["rotate", "90", "mirror", "v", "crop", "0", "1", "2", "3"]
+            .iter()
+            .chain(["pad", "1", "wall"].iter())
+            .map(|a| a.to_string())
+            .collect();
+        assert_eq!(
+            parse_transformaciones(&operaciones),
+            Ok(vec![
+                Transformacion::Rotar(90),
+                Transformacion::EspejarVertical,
+                Transformacion::Recortar {
+                    x: 0,
+                    y: 1,
+                    ancho: 2,
+                    alto: 3
+                },
+                Transformacion::Rellenar {
+                    margen: 1,
+                    relleno: Objeto::Pared
+                },
+            ])
+        );
+        for invalidas in [&["mirror", "x"][..], &["crop", "0", "1"], &["spin"], &[]] {
+            let invalidas: Vec<String> = invalidas.iter().map(|a| a.to_string()).collect();
+            assert!(matches!(
+                parse_transformaciones(&invalidas),
+                Err(ErrorCli::Uso(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_detonate_archivo_de_error_sin_salida() {
+        let args: Vec<String> = ["maze.txt", "--x", "0", "--y", "0", "--error-file"]
+            .iter()
+            .map(|a| a.to_string())
+            .collect();
+        assert_eq!(
+            detonate(&args),
+            Err(ErrorCli::Uso(
+                "ERROR: La opción --error-file requiere --out.".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_detonate_sin_coordenadas() {
+        let args = vec!["maze.txt".to_string(), "--x".to_string(), "0".to_string()];
+        assert_eq!(
+            detonate(&args),
+            Err(ErrorCli::Uso("ERROR: Falta la opción --y.".to_string()))
+        );
+    }
+}
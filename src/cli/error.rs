@@ -0,0 +1,165 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Código de salida cuando los argumentos son incorrectos.
+pub const SALIDA_USO: i32 = 2;
+/// Código de salida cuando el archivo de tablero no tiene un formato válido.
+pub const SALIDA_TABLERO_INVALIDO: i32 = 3;
+/// Código de salida cuando falla una lectura o escritura de archivos.
+pub const SALIDA_ARCHIVO: i32 = 4;
+/// Código de salida cuando no se puede detonar en las coordenadas pedidas.
+pub const SALIDA_DETONACION: i32 = 5;
+/// Código de salida cuando una repetición no produce el tablero esperado.
+pub const SALIDA_VERIFICACION: i32 = 6;
+
+/// Enumeración que representa las clases de error que puede devolver la línea de comandos.
+#[derive(PartialEq, Debug, Clone)]
+pub enum ErrorCli {
+    /// Los argumentos están mal formados o el subcomando no existe.
+    Uso(String),
+    /// El archivo de tablero no tiene un formato válido.
+    TableroInvalido(String),
+    /// Falló una operación de lectura o escritura.
+    Archivo(String),
+    /// No se pudo detonar en las coordenadas pedidas.
+    Detonacion(String),
+    /// El resultado no coincide con el esperado.
+    Verificacion(String),
+}
+
+impl ErrorCli {
+    /// Devuelve el código de salida del proceso correspondiente a la clase de error.
+    pub fn codigo_salida(&self) -> i32 {
+        match self {
+            ErrorCli::Uso(_) => SALIDA_USO,
+            ErrorCli::TableroInvalido(_) => SALIDA_TABLERO_INVALIDO,
+            ErrorCli::Archivo(_) => SALIDA_ARCHIVO,
+            ErrorCli::Detonacion(_) => SALIDA_DETONACION,
+            ErrorCli::Verificacion(_) => SALIDA_VERIFICACION,
+        }
+    }
+
+    /// Clasifica un error devuelto por `crear_tablero`: los datos inválidos son un tablero
+    /// inválido y el resto son errores de archivo.
+    pub fn al_cargar(error: io::Error) -> ErrorCli {
+        let mensaje = format!("ERROR: {}", error);
+        match error.kind() {
+            io::ErrorKind::InvalidData => ErrorCli::TableroInvalido(mensaje),
+            _ => ErrorCli::Archivo(mensaje),
+        }
+    }
+
+    /// Escribe el mensaje de error en `output_dir`, en un archivo con el nombre del archivo de
+    /// entrada, y devuelve el mismo error para seguir propagándolo. Si el archivo no se puede
+    /// escribir, lo informa por la salida de errores.
+    ///
+    /// # Argumentos
+    ///
+    /// * `output_dir`: El directorio donde se guarda el archivo de error.
+    /// * `input_file`: El nombre del archivo de entrada, que se usa como nombre de salida.
+    ///
+    pub fn guardar_en_archivo(self, output_dir: &str, input_file: &str) -> ErrorCli {
+        let texto = self.to_string();
+        self.guardar_texto_en_archivo(output_dir, input_file, &texto)
+    }
+
+    /// Igual que `guardar_en_archivo`, pero escribe `texto` en lugar del mensaje del error.
+    pub fn guardar_texto_en_archivo(
+        self,
+        output_dir: &str,
+        input_file: &str,
+        texto: &str,
+    ) -> ErrorCli {
+        let output_file_path = Path::new(output_dir).join(input_file);
+        let resultado =
+            File::create(output_file_path).and_then(|mut file| write!(file, "{}", texto));
+        if let Err(e) = resultado {
+            eprintln!("ERROR: No se pudo escribir el archivo de error: {}", e);
+        }
+        self
+    }
+
+    /// Convierte un error de escritura en un error de archivo con el contexto indicado.
+    pub fn al_escribir(contexto: &str, error: io::Error) -> ErrorCli {
+        ErrorCli::Archivo(format!("ERROR: {}: {}", contexto, error))
+    }
+}
+
+impl fmt::Display for ErrorCli {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorCli::Uso(mensaje)
+            | ErrorCli::TableroInvalido(mensaje)
+            | ErrorCli::Archivo(mensaje)
+            | ErrorCli::Detonacion(mensaje)
+            | ErrorCli::Verificacion(mensaje) => write!(f, "{}", mensaje),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::fs::remove_dir_all;
+
+    const TEMP_DIR_NAME: &str = "test_temp_dir";
+
+    #[test]
+    fn test_codigos_de_salida_distintos() {
+        let codigos = [
+            ErrorCli::Uso(String::new()).codigo_salida(),
+            ErrorCli::TableroInvalido(String::new()).codigo_salida(),
+            ErrorCli::Archivo(String::new()).codigo_salida(),
+            ErrorCli::Detonacion(String::new()).codigo_salida(),
+            ErrorCli::Verificacion(String::new()).codigo_salida(),
+        ];
+        assert_eq!(codigos, [2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_al_cargar() {
+        let invalido = io::Error::new(io::ErrorKind::InvalidData, "Símbolo no válido");
+        assert_eq!(
+            ErrorCli::al_cargar(invalido),
+            ErrorCli::TableroInvalido("ERROR: Símbolo no válido".to_string())
+        );
+
+        let inexistente = io::Error::new(io::ErrorKind::NotFound, "No existe");
+        assert_eq!(
+            ErrorCli::al_cargar(inexistente).codigo_salida(),
+            SALIDA_ARCHIVO
+        );
+    }
+
+    #[test]
+    fn test_guardar_en_archivo() {
+        // Crear un directorio temporal para las pruebas
+        if let Err(err) = fs::create_dir(TEMP_DIR_NAME) {
+            panic!("Error al crear el directorio temporal: {:?}", err);
+        }
+
+        let temp_dir_path = &TEMP_DIR_NAME;
+        let error_message = "Este es un mensaje de error de prueba";
+        let input_file = "archivo_prueba.txt";
+
+        let error = ErrorCli::Detonacion(error_message.to_string());
+        let devuelto = error.clone().guardar_en_archivo(temp_dir_path, input_file);
+        assert_eq!(devuelto, error);
+
+        // Leer el contenido del archivo y verificar si es igual al mensaje de error
+        let output_file_path = Path::new(temp_dir_path).join(input_file);
+        if let Ok(contents) = fs::read_to_string(output_file_path) {
+            assert_eq!(contents, error_message);
+        } else {
+            panic!("Error al leer el archivo de salida");
+        }
+
+        // Eliminar el directorio temporal después de la prueba
+        if let Err(err) = remove_dir_all(temp_dir_path) {
+            panic!("Error al eliminar el directorio temporal: {:?}", err);
+        }
+    }
+}
@@ -1,51 +1,78 @@
-mod tablero;
+mod cli;
+use cli::error::ErrorCli;
 use std::env;
-use std::fs::File;
-use std::io::Write;
-use std::path::Path;
-use tablero::{crear_tablero, guardar_tablero};
+use std::process;
+use tp_individual::tablero::{crear_tablero, guardar_tablero};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
-    if let Err(err_msg) = run(args) {
-        println!("{}", err_msg);
+    if let Err(error) = run(args) {
+        eprintln!("{}", error);
+        process::exit(error.codigo_salida());
     }
 }
 
-fn run(args: Vec<String>) -> Result<(), String> {
+fn run(args: Vec<String>) -> Result<(), ErrorCli> {
+    match args.get(1).map(String::as_str) {
+        Some("--help") | Some("-h") => {
+            print!("{}", cli::AYUDA);
+            Ok(())
+        }
+        Some(subcomando) if cli::es_subcomando(subcomando) => cli::ejecutar(subcomando, &args[2..]),
+        _ => run_posicional(args),
+    }
+}
+
+/// Modo original de invocación: `maze.txt /path/to/output_dir/ x y`. Además de informarse por
+/// la salida de errores, los errores de carga, detonación y guardado se escriben en el archivo de
+/// salida con el mismo texto que escribía el formato original (ver `detonar_y_guardar`).
+fn run_posicional(args: Vec<String>) -> Result<(), ErrorCli> {
     if args.len() != 5 {
-        return Err(
-            "Uso incorrecto. Ejemplo: cargo run -- maze.txt /path/to/output_dir/ x y".to_string(),
-        );
+        return Err(ErrorCli::Uso(format!(
+            "Uso incorrecto. Ejemplo: cargo run -- maze.txt /path/to/output_dir/ x y\n\n{}",
+            cli::AYUDA
+        )));
     }
 
     let input_file = &args[1];
     let output_dir = &args[2];
-    let x = parse_coordenadas(&args[3], "X")?;
-    let y = parse_coordenadas(&args[4], "Y")?;
+    let x = parse_coordenadas(&args[3], "X").map_err(ErrorCli::Uso)?;
+    let y = parse_coordenadas(&args[4], "Y").map_err(ErrorCli::Uso)?;
 
-    let mut tablero = match crear_tablero(input_file) {
-        Ok(t) => t,
-        Err(e) => {
-            guardar_error(output_dir, &format!("ERROR: {}", e), input_file);
-            return Ok(());
-        }
-    };
+    detonar_y_guardar(input_file, output_dir, x, y)
+        .map_err(|(error, texto)| error.guardar_texto_en_archivo(output_dir, input_file, &texto))
+}
 
-    match tablero.detonar(x, y) {
-        Ok(_) => match guardar_tablero(output_dir, &tablero, input_file) {
-            Ok(_) => Ok(()),
-            Err(e) => {
-                guardar_error(output_dir, &format!("ERROR: {}", e), input_file);
-                Ok(())
-            }
-        },
-        Err(err_msg) => {
-            guardar_error(output_dir, &format!("ERROR: {}", err_msg), input_file);
-            Ok(())
-        }
-    }
+/// Carga el tablero, detona y guarda el resultado.
+///
+/// # Devuelve
+///
+/// Si algo falla, devuelve el error junto con el texto que el formato original escribe en el
+/// archivo de salida: el mensaje con el prefijo `ERROR: `, aunque ya lo tenga, como en
+/// `ERROR: ERROR: No es una bomba, no se puede detonar.`, y sin el contexto que se muestra
+/// por la salida de errores.
+fn detonar_y_guardar(
+    input_file: &str,
+    output_dir: &str,
+    x: i32,
+    y: i32,
+) -> Result<(), (ErrorCli, String)> {
+    let texto_original = |mensaje: &dyn std::fmt::Display| format!("ERROR: {}", mensaje);
+    let mut tablero = crear_tablero(input_file).map_err(|e| {
+        let texto = texto_original(&e);
+        (ErrorCli::al_cargar(e), texto)
+    })?;
+    tablero
+        .detonar(x, y)
+        .map_err(|e| (ErrorCli::Detonacion(e.clone()), texto_original(&e)))?;
+    guardar_tablero(output_dir, &tablero, input_file).map_err(|e| {
+        let texto = texto_original(&e);
+        (
+            ErrorCli::al_escribir("No se pudo guardar el tablero", e),
+            texto,
+        )
+    })
 }
 
 fn parse_coordenadas(coord_str: &str, coord_nombre: &str) -> Result<i32, String> {
@@ -57,28 +84,9 @@ fn parse_coordenadas(coord_str: &str, coord_nombre: &str) -> Result<i32, String>
     })
 }
 
-fn guardar_error(output_dir: &str, error_message: &str, input_file: &str) {
-    let output_file_name = input_file;
-    let output_file_path = Path::new(output_dir).join(output_file_name);
-    match File::create(output_file_path) {
-        Ok(mut file) => {
-            if write!(file, "{}", error_message).is_err() {
-                println!("Error al escribir el mensaje de error en el archivo.");
-            }
-        }
-        Err(_) => {
-            println!("Error al crear el archivo de salida.");
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
-    use std::fs::remove_dir_all;
-
-    const TEMP_DIR_NAME: &str = "test_temp_dir";
 
     #[test]
     fn test_parse_coordenadas_valid() {
@@ -96,30 +104,32 @@ mod tests {
     }
 
     #[test]
-    fn test_guardar_error() {
-        // Crear un directorio temporal para las pruebas
-        if let Err(err) = fs::create_dir(TEMP_DIR_NAME) {
-            assert!(false, "Error al crear el directorio temporal: {:?}", err);
-            return;
-        }
-
-        let temp_dir_path = &TEMP_DIR_NAME;
-        let error_message = "Este es un mensaje de error de prueba";
-        let input_file = "archivo_prueba.txt";
-
-        guardar_error(temp_dir_path, error_message, input_file);
-
-        // Leer el contenido del archivo y verificar si es igual al mensaje de error
-        let output_file_path = Path::new(temp_dir_path).join(input_file);
-        if let Ok(contents) = fs::read_to_string(output_file_path) {
-            assert_eq!(contents, error_message);
-        } else {
-            assert!(false, "Error al leer el archivo de salida");
-        }
+    fn test_archivo_de_error_conserva_el_texto_original() {
+        let maze = "tests/casos/sin_bomba/tablero.txt";
+        let salida = std::env::temp_dir().join("tp_main_texto_original");
+        let archivo = salida.join(maze);
+        assert!(archivo
+            .parent()
+            .is_some_and(|directorio| std::fs::create_dir_all(directorio).is_ok()));
+        let salida = salida.to_string_lossy().into_owned();
+
+        let args: Vec<String> = ["tp_individual", maze, &salida, "1", "1"]
+            .iter()
+            .map(|a| a.to_string())
+            .collect();
+        assert!(matches!(run(args), Err(ErrorCli::Detonacion(_))));
+        assert_eq!(
+            std::fs::read_to_string(archivo).unwrap_or_default(),
+            "ERROR: ERROR: No es una bomba, no se puede detonar."
+        );
 
-        // Eliminar el directorio temporal después de la prueba
-        if let Err(err) = remove_dir_all(temp_dir_path) {
-            assert!(false, "Error al eliminar el directorio temporal: {:?}", err);
-        }
+        let Err((error, texto)) = detonar_y_guardar(maze, "/directorio/inexistente", 0, 0) else {
+            panic!("Se guardó el tablero en un directorio inexistente");
+        };
+        assert_eq!(
+            error.to_string(),
+            "ERROR: No se pudo guardar el tablero: Directorio de salida no existe"
+        );
+        assert_eq!(texto, "ERROR: Directorio de salida no existe");
     }
 }
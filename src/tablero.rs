@@ -1,15 +1,52 @@
+pub mod analisis;
+pub mod animacion;
+pub mod diferencias;
+pub mod estadisticas;
+pub mod generacion;
+pub mod grafo;
+pub mod historial;
+pub mod huella;
+pub mod interruptores;
 pub mod model;
+pub mod portales;
+pub mod renderizado;
+pub mod repeticion;
+pub mod resolucion;
+pub mod transformacion;
+pub mod validacion;
 use model::direccion::Direccion;
-use model::objeto::{convertir_simbolos, Objeto};
+use model::direccion::Direcciones;
+use model::enemigo::TipoDeEnemigo;
+use model::evento::Evento;
+use model::forma::Forma;
+use model::objeto::{convertir_celda, convertir_celda_a_simbolo, es_de_suelo, Objeto};
+use std::collections::HashSet;
 use std::fs::File;
 use std::io::Write;
 use std::io::{self, BufRead, BufReader};
 use std::path::Path;
 
+/// Línea que, escrita antes de la primera fila de un archivo de tablero, indica que el tablero
+/// es toroidal.
+pub const DIRECTIVA_TOROIDAL: &str = "@toroidal";
+
+/// Suelo de las celdas que no tienen suelo.
+static SIN_SUELO: Objeto = Objeto::Vacio;
+
 /// Representa un tablero del juego que contiene una cuadrícula de objetos y su tamaño.
+#[derive(Debug, Clone)]
 pub struct Tablero {
     pub cuadricula: Vec<Vec<Objeto>>,
     pub tamaño: i32,
+    /// Sucesos producidos por la última detonación, incluidas las reacciones en cadena.
+    pub eventos: Vec<Evento>,
+    /// Indica si los bordes del tablero están conectados: un rayo que sale por un lado vuelve
+    /// a entrar por el lado opuesto.
+    pub toroidal: bool,
+    /// Capa de suelo que está debajo de los objetos de `cuadricula`, con las mismas
+    /// dimensiones: los desvíos, espejos, divisores, portales e interruptores sobre los que hay
+    /// un enemigo, una bomba o una roca. Está vacía si ninguna celda tiene suelo.
+    pub suelo: Vec<Vec<Objeto>>,
 }
 
 impl Tablero {
@@ -22,8 +59,35 @@ impl Tablero {
     pub fn new(tamaño: i32) -> Tablero {
         let cuadricula = Vec::new();
         Tablero {
-            cuadricula, tamaño
+            cuadricula,
+            tamaño,
+            eventos: Vec::new(),
+            toroidal: false,
+            suelo: Vec::new(),
+        }
+    }
+
+    /// Devuelve las coordenadas `(x, y)` de todas las bombas del tablero, recorriendo
+    /// la cuadrícula por filas.
+    pub fn posiciones_bombas(&self) -> Vec<(i32, i32)> {
+        let mut bombas = Vec::new();
+        for (y, fila) in self.cuadricula.iter().enumerate() {
+            for (x, objeto) in fila.iter().enumerate() {
+                if let Objeto::Bomba(_, _, _) | Objeto::BombaDeArea(_, _, _) = objeto {
+                    bombas.push((x as i32, y as i32));
+                }
+            }
         }
+        bombas
+    }
+
+    /// Devuelve la cantidad de enemigos que quedan en el tablero.
+    pub fn cantidad_enemigos(&self) -> usize {
+        self.cuadricula
+            .iter()
+            .flatten()
+            .filter(|objeto| matches!(objeto, Objeto::Enemigo(_, _, _)))
+            .count()
     }
 
     fn obtener_objeto_en_posicion(&self, x: usize, y: usize) -> Option<&Objeto> {
@@ -34,6 +98,61 @@ impl Tablero {
         }
     }
 
+    /// Devuelve el objeto de la capa de suelo en `(x, y)`, o `Objeto::Vacio` si la celda no
+    /// tiene suelo.
+    pub fn suelo_en(&self, x: usize, y: usize) -> &Objeto {
+        self.suelo
+            .get(y)
+            .and_then(|fila| fila.get(x))
+            .unwrap_or(&SIN_SUELO)
+    }
+
+    /// Devuelve el suelo de `(x, y)` para modificarlo, creando la capa de suelo si el tablero
+    /// todavía no tenía.
+    pub fn suelo_en_mut(&mut self, x: usize, y: usize) -> &mut Objeto {
+        if self.suelo.is_empty() {
+            self.suelo = self
+                .cuadricula
+                .iter()
+                .map(|fila| vec![Objeto::Vacio; fila.len()])
+                .collect();
+        }
+        &mut self.suelo[y][x]
+    }
+
+    /// Devuelve el símbolo de la celda `(x, y)`, con su suelo si tiene, o `None` si la celda
+    /// está fuera de la cuadrícula.
+    pub fn simbolo_en(&self, x: usize, y: usize) -> Option<String> {
+        let ocupante = self.cuadricula.get(y)?.get(x)?;
+        Some(convertir_celda_a_simbolo(ocupante, self.suelo_en(x, y)))
+    }
+
+    /// Devuelve las posiciones `(x, y)` y los objetos de ambas capas, recorriendo la
+    /// cuadrícula por filas: en cada celda, primero el ocupante y después el suelo, si tiene.
+    pub fn objetos_con_suelo(&self) -> Vec<((usize, usize), &Objeto)> {
+        let mut objetos = Vec::new();
+        for (y, fila) in self.cuadricula.iter().enumerate() {
+            for (x, ocupante) in fila.iter().enumerate() {
+                objetos.push(((x, y), ocupante));
+                let suelo = self.suelo_en(x, y);
+                if *suelo != Objeto::Vacio {
+                    objetos.push(((x, y), suelo));
+                }
+            }
+        }
+        objetos
+    }
+
+    /// Devuelve el objeto que guía o acciona los rayos que pasan por `(x, y)`: el de la
+    /// cuadrícula si es un objeto de suelo suelto, o el de la capa de suelo debajo del ocupante.
+    fn baldosa_en(&self, x: usize, y: usize) -> Option<&Objeto> {
+        let ocupante = self.obtener_objeto_en_posicion(x, y)?;
+        if es_de_suelo(ocupante) {
+            return Some(ocupante);
+        }
+        Some(self.suelo_en(x, y)).filter(|suelo| **suelo != Objeto::Vacio)
+    }
+
     /// Detona una bomba en las coordenadas especificadas.
     ///
     /// # Argumentos
@@ -46,6 +165,25 @@ impl Tablero {
     /// Devuelve `Ok(())` si la bomba se detonó correctamente, o `Err(String)` si no se pudo detonar
     /// porque no había una bomba en las coordenadas especificadas.
     pub fn detonar(&mut self, x: i32, y: i32) -> Result<(), String> {
+        self.eventos.clear();
+        let resultado = self.detonar_bomba(x, y);
+        self.liberar_suelo();
+        resultado
+    }
+
+    /// Devuelve a la cuadrícula el suelo de las celdas que se quedaron sin ocupante, para que
+    /// una baldosa sola se represente igual que al leerla de un archivo.
+    fn liberar_suelo(&mut self) {
+        for (y, fila) in self.suelo.iter_mut().enumerate() {
+            for (x, suelo) in fila.iter_mut().enumerate() {
+                if *suelo != Objeto::Vacio && self.cuadricula[y][x] == Objeto::Vacio {
+                    self.cuadricula[y][x] = std::mem::replace(suelo, Objeto::Vacio);
+                }
+            }
+        }
+    }
+
+    fn detonar_bomba(&mut self, x: i32, y: i32) -> Result<(), String> {
         let x_usize = x as usize;
         let y_usize = y as usize;
 
@@ -54,28 +192,22 @@ impl Tablero {
             .get(y_usize)
             .and_then(|row| row.get(x_usize))
         {
-            Some(&Objeto::Bomba(traspaso, alcance)) => {
+            Some(&Objeto::Bomba(traspaso, alcance, direcciones)) => {
+                self.cuadricula[y_usize][x_usize] = Objeto::Vacio;
+                self.eventos.push(Evento::Detonacion(x, y));
+                for direccion in direcciones.direcciones() {
+                    self.detonar_en_direccion(
+                        (x, y, x_usize, y_usize, traspaso),
+                        direccion,
+                        alcance,
+                    );
+                }
+                Ok(())
+            }
+            Some(&Objeto::BombaDeArea(forma, alcance, ocluida)) => {
                 self.cuadricula[y_usize][x_usize] = Objeto::Vacio;
-                self.detonar_en_direccion(
-                    (x, y, x_usize, y_usize, traspaso),
-                    Direccion::Arriba,
-                    alcance,
-                );
-                self.detonar_en_direccion(
-                    (x, y, x_usize, y_usize, traspaso),
-                    Direccion::Abajo,
-                    alcance,
-                );
-                self.detonar_en_direccion(
-                    (x, y, x_usize, y_usize, traspaso),
-                    Direccion::Izquierda,
-                    alcance,
-                );
-                self.detonar_en_direccion(
-                    (x, y, x_usize, y_usize, traspaso),
-                    Direccion::Derecha,
-                    alcance,
-                );
+                self.eventos.push(Evento::Detonacion(x, y));
+                self.detonar_en_area((x, y), forma, alcance, ocluida);
                 Ok(())
             }
             _ => Err("ERROR: No es una bomba, no se puede detonar.".to_string()),
@@ -92,41 +224,175 @@ impl Tablero {
         let mut i = 1;
         let mut seguir_detonando = true;
         while i <= alcance && seguir_detonando {
-            let (nuevo_x, nuevo_y) =
-                Self::calcular_nueva_posicion(x_usize, y_usize, direccion.clone(), i);
-            if nuevo_x < self.tamaño as usize && nuevo_y < self.tamaño as usize {
-                match self.obtener_objeto_en_posicion(nuevo_x, nuevo_y) {
-                    Some(Objeto::Desvio(dir)) => {
+            // En un tablero toroidal el rayo puede dar la vuelta completa; se corta al volver a
+            // la celda de la que salió para no alcanzar dos veces las mismas celdas.
+            let siguiente = self
+                .calcular_nueva_posicion(x_usize, y_usize, &direccion, i)
+                .filter(|&posicion| posicion != (x_usize, y_usize));
+            if let Some((nuevo_x, nuevo_y)) = siguiente {
+                // Primero recibe la explosión el ocupante de la celda; si el rayo no se detiene
+                // ahí, lo guía el objeto de suelo que haya en la celda.
+                seguir_detonando = self.obtener_objeto_en_posicion(nuevo_x, nuevo_y).is_some()
+                    && self.detonar_en_posicion(nuevo_x, nuevo_y, traspaso, x, y)
+                    && self.guiar_rayo((x, y, nuevo_x, nuevo_y, traspaso), &direccion, alcance - i);
+            } else {
+                seguir_detonando = false;
+            }
+            i += 1;
+        }
+    }
+
+    /// Aplica el objeto de suelo de la celda `(nuevo_x, nuevo_y)` al rayo que llega a ella
+    /// viajando en `direccion`: los desvíos lanzan un rayo nuevo, los espejos lo reflejan, los
+    /// divisores lo parten y los portales lo trasladan a su pareja, cuyo ocupante también
+    /// recibe la explosión.
+    ///
+    /// # Argumentos
+    ///
+    /// * `args`: La bomba de origen, la celda alcanzada y si el rayo es de traspaso.
+    /// * `direccion`: La dirección en la que llega el rayo.
+    /// * `alcance`: Las celdas que le quedan por recorrer al rayo.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve `true` si el rayo sigue de largo en la misma dirección.
+    fn guiar_rayo(
+        &mut self,
+        args: (i32, i32, usize, usize, bool),
+        direccion: &Direccion,
+        alcance: i32,
+    ) -> bool {
+        let (x, y, nuevo_x, nuevo_y, traspaso) = args;
+        match self.baldosa_en(nuevo_x, nuevo_y).cloned() {
+            Some(Objeto::Desvio(dir, _)) => {
+                self.detonar_en_direccion(args, dir, alcance);
+                true
+            }
+            Some(Objeto::Espejo(espejo)) => {
+                self.detonar_en_direccion(args, espejo.reflejar(direccion), alcance);
+                false
+            }
+            Some(Objeto::Divisor) => {
+                let horario = direccion.rotada_horario();
+                let antihorario = horario.rotada_horario().rotada_horario();
+                for rama in [horario, antihorario] {
+                    self.detonar_en_direccion(args, rama, alcance);
+                }
+                false
+            }
+            Some(Objeto::Portal(_)) => match self.salida_de_portal(nuevo_x, nuevo_y) {
+                Some((salida_x, salida_y)) => {
+                    // El ocupante del portal de salida recibe el rayo como el de cualquier
+                    // otra celda, y puede detenerlo.
+                    if self.detonar_en_posicion(salida_x, salida_y, traspaso, x, y) {
                         self.detonar_en_direccion(
-                            (x, y, nuevo_x, nuevo_y, traspaso),
-                            dir.clone(),
-                            alcance - i,
+                            (x, y, salida_x, salida_y, traspaso),
+                            direccion.clone(),
+                            alcance,
                         );
                     }
-                    Some(_) => {
-                        seguir_detonando =
-                            self.detonar_en_posicion(nuevo_x, nuevo_y, traspaso, x, y);
-                    }
-                    None => seguir_detonando = false,
+                    false
                 }
-            } else {
-                seguir_detonando = false;
+                None => true,
+            },
+            _ => true,
+        }
+    }
+
+    /// Alcanza todas las celdas que cubre la forma de la explosión, de adentro hacia afuera.
+    /// Las rocas no reciben la explosión pero tampoco la detienen; si está ocluida, no llega a
+    /// las celdas que tienen una pared en la línea recta que las une con la bomba. Un alcance
+    /// mayor que la distancia entre esquinas opuestas del tablero, medida según la forma, se
+    /// recorta, porque más allá no hay celdas. En un tablero toroidal
+    /// la explosión da la vuelta por los bordes y cada celda la recibe una sola vez.
+    fn detonar_en_area(&mut self, origen: (i32, i32), forma: Forma, alcance: i32, ocluida: bool) {
+        let (x, y) = origen;
+        let alcance = alcance.min(forma.distancia_maxima(self.tamaño));
+        let mut alcanzadas = HashSet::from([(x as usize, y as usize)]);
+        for (dx, dy) in forma.desplazamientos(alcance) {
+            let Some(celda) = self.posicion_en_tablero(i64::from(x + dx), i64::from(y + dy)) else {
+                continue;
+            };
+            if alcanzadas.contains(&celda)
+                || self.obtener_objeto_en_posicion(celda.0, celda.1).is_none()
+                || (ocluida && !self.hay_linea_de_vision(origen, (x + dx, y + dy)))
+            {
+                continue;
+            }
+            alcanzadas.insert(celda);
+            self.detonar_en_posicion(celda.0, celda.1, false, x, y);
+        }
+    }
+
+    /// Indica si ninguna celda entre `desde` y `hasta` (sin contar los extremos) es una pared
+    /// o una compuerta cerrada, recorriendo la línea que las une con el algoritmo de Bresenham.
+    fn hay_linea_de_vision(&self, desde: (i32, i32), hasta: (i32, i32)) -> bool {
+        let (mut x, mut y) = desde;
+        let (dx, dy) = ((hasta.0 - x).abs(), -(hasta.1 - y).abs());
+        let (paso_x, paso_y) = ((hasta.0 - x).signum(), (hasta.1 - y).signum());
+        let mut error = dx + dy;
+        loop {
+            let doble = 2 * error;
+            if doble >= dy {
+                error += dy;
+                x += paso_x;
+            }
+            if doble <= dx {
+                error += dx;
+                y += paso_y;
+            }
+            if (x, y) == hasta {
+                return true;
+            }
+            if let Some(Objeto::Pared | Objeto::Compuerta(_, false)) = self
+                .posicion_en_tablero(i64::from(x), i64::from(y))
+                .and_then(|(x, y)| self.obtener_objeto_en_posicion(x, y))
+            {
+                return false;
             }
-            i += 1;
         }
     }
 
+    /// Calcula la celda que está `paso` celdas más allá de `(x_usize, y_usize)` en la
+    /// dirección indicada.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve `None` si la celda queda fuera del tablero. En un tablero toroidal nunca queda
+    /// afuera: las coordenadas dan la vuelta por el lado opuesto.
     fn calcular_nueva_posicion(
+        &self,
         x_usize: usize,
         y_usize: usize,
-        direccion: Direccion,
+        direccion: &Direccion,
         paso: i32,
-    ) -> (usize, usize) {
-        match direccion {
-            Direccion::Arriba => (x_usize, y_usize.wrapping_sub(paso as usize)),
-            Direccion::Abajo => (x_usize, y_usize + paso as usize),
-            Direccion::Derecha => (x_usize + paso as usize, y_usize),
-            Direccion::Izquierda => (x_usize.wrapping_sub(paso as usize), y_usize),
+    ) -> Option<(usize, usize)> {
+        let (dx, dy) = direccion.desplazamiento();
+        let nuevo_x = x_usize as i64 + dx as i64 * paso as i64;
+        let nuevo_y = y_usize as i64 + dy as i64 * paso as i64;
+        self.posicion_en_tablero(nuevo_x, nuevo_y)
+    }
+
+    /// Devuelve la celda que corresponde a las coordenadas `(x, y)`, o `None` si quedan fuera
+    /// del tablero. En un tablero toroidal dan la vuelta por el lado opuesto.
+    fn posicion_en_tablero(&self, x: i64, y: i64) -> Option<(usize, usize)> {
+        let tamaño = self.tamaño as i64;
+        if self.toroidal && tamaño > 0 {
+            Some((x.rem_euclid(tamaño) as usize, y.rem_euclid(tamaño) as usize))
+        } else if (0..tamaño).contains(&x) && (0..tamaño).contains(&y) {
+            Some((x as usize, y as usize))
+        } else {
+            None
+        }
+    }
+
+    /// Indica si un enemigo del tipo indicado pierde vida al ser alcanzado por un rayo,
+    /// según si el rayo es de una bomba de traspaso y si esa bomba ya lo había golpeado.
+    fn recibe_daño(tipo: TipoDeEnemigo, traspaso: bool, ya_afectado: bool) -> bool {
+        match tipo {
+            TipoDeEnemigo::Blindado => traspaso && !ya_afectado,
+            TipoDeEnemigo::Vulnerable => true,
+            TipoDeEnemigo::Comun | TipoDeEnemigo::Explosivo(_) => !ya_afectado,
         }
     }
 
@@ -139,25 +405,49 @@ impl Tablero {
         y_original: i32,
     ) -> bool {
         match self.cuadricula[y][x] {
-            Objeto::Enemigo(ref mut vida, ref mut bombas_afectadas) => {
-                if !bombas_afectadas.contains(&(x_original, y_original)) {
-                    bombas_afectadas.insert((x_original, y_original));
-                    if *vida > 1 {
-                        *vida -= 1;
-                    } else {
-                        self.cuadricula[y][x] = Objeto::Vacio;
-                    }
+            Objeto::Pared | Objeto::Compuerta(_, false) => return false,
+            Objeto::Roca if !traspaso => return false,
+            _ => self.eventos.push(Evento::Alcance(
+                x as i32,
+                y as i32,
+                (x_original, y_original),
+            )),
+        }
+
+        match self.cuadricula[y][x] {
+            Objeto::Enemigo(ref mut vida, ref mut bombas_afectadas, tipo)
+                if Self::recibe_daño(
+                    tipo,
+                    traspaso,
+                    bombas_afectadas.contains(&(x_original, y_original)),
+                ) =>
+            {
+                bombas_afectadas.insert((x_original, y_original));
+                *vida -= 1;
+                let vida_restante = *vida;
+                if vida_restante == 0 {
+                    self.cuadricula[y][x] = Objeto::Vacio;
+                }
+                self.eventos.push(Evento::Impacto(
+                    x as i32,
+                    y as i32,
+                    (x_original, y_original),
+                    vida_restante,
+                ));
+                if let (0, TipoDeEnemigo::Explosivo(alcance)) = (vida_restante, tipo) {
+                    self.cuadricula[y][x] = Objeto::Bomba(false, alcance, Direcciones::ORTOGONALES);
+                    let _some = self.detonar_bomba(x as i32, y as i32);
                 }
-                true
             }
-            Objeto::Bomba(_, _) => {
-                let _some = self.detonar(x as i32, y as i32);
-                true
+            Objeto::Bomba(_, _, _) | Objeto::BombaDeArea(_, _, _) => {
+                let _some = self.detonar_bomba(x as i32, y as i32);
             }
-            Objeto::Roca => traspaso,
-            Objeto::Pared => false,
-            _ => true,
+            _ => {}
         }
+        if let Some(&Objeto::Interruptor(id)) = self.baldosa_en(x, y) {
+            self.activar_interruptor(x, y, id);
+        }
+        true
     }
 }
 
@@ -169,24 +459,51 @@ impl Tablero {
 ///
 /// # Devoluciones
 ///
-/// Devuelve un resultado que contiene el tablero creado o un error de E/S.
+/// Devuelve un resultado que contiene el tablero creado o un error de E/S. Si el archivo
+/// contiene símbolos inválidos o portales que no tienen exactamente una pareja, el error es de
+/// tipo `io::ErrorKind::InvalidData`.
 ///
 pub fn crear_tablero(input_file: &str) -> Result<Tablero, io::Error> {
     let file = File::open(input_file)?;
-    let reader = BufReader::new(file);
+    leer_tablero(BufReader::new(file))
+}
 
+/// Crea un tablero leyendo su contenido, con el formato de los archivos de entrada, desde
+/// cualquier origen que implemente `BufRead` (por ejemplo, `contenido.as_bytes()`). Si la
+/// primera línea es `DIRECTIVA_TOROIDAL`, el tablero es toroidal.
+///
+/// # Argumentos
+///
+/// * `reader`: El origen desde donde se leen las líneas del tablero.
+///
+/// # Devoluciones
+///
+/// Devuelve el tablero creado o un error con las mismas clases que `crear_tablero`.
+///
+pub fn leer_tablero<R: BufRead>(reader: R) -> Result<Tablero, io::Error> {
     let mut tablero = None;
+    let mut toroidal = false;
+    let mut suelo: Vec<Vec<Objeto>> = Vec::new();
 
     for line in reader.lines() {
         let line = line?;
+        if tablero.is_none() && line.trim() == DIRECTIVA_TOROIDAL {
+            toroidal = true;
+            continue;
+        }
         let mut row = Vec::new();
+        let mut fila_de_suelo = Vec::new();
 
         for simbolo in line.split_whitespace() {
-            match convertir_simbolos(simbolo) {
-                Ok(objeto) => row.push(objeto.clone()),
-                Err(err) => return Err(io::Error::new(io::ErrorKind::Other, err)),
+            match convertir_celda(simbolo) {
+                Ok((ocupante, suelo)) => {
+                    row.push(ocupante);
+                    fila_de_suelo.push(suelo);
+                }
+                Err(err) => return Err(io::Error::new(io::ErrorKind::InvalidData, err)),
             }
         }
+        suelo.push(fila_de_suelo);
 
         if tablero.is_none() {
             let size = row.len() as i32;
@@ -198,7 +515,22 @@ pub fn crear_tablero(input_file: &str) -> Result<Tablero, io::Error> {
         }
     }
 
-    tablero.ok_or_else(|| io::Error::new(io::ErrorKind::Other, "No se pudo crear el tablero"))
+    let mut tablero = tablero.ok_or_else(|| io::Error::other("No se pudo crear el tablero"))?;
+    tablero.toroidal = toroidal;
+    if suelo
+        .iter()
+        .flatten()
+        .any(|objeto| *objeto != Objeto::Vacio)
+    {
+        tablero.suelo = suelo;
+    }
+    if let Some(((x, y), descripcion)) = tablero.portales_sin_pareja().into_iter().next() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("({}, {}): {}", x, y, descripcion),
+        ));
+    }
+    Ok(tablero)
 }
 
 /// Guarda el contenido de un tablero en un archivo de salida en el directorio especificado.
@@ -220,39 +552,41 @@ pub fn guardar_tablero(
 ) -> Result<(), io::Error> {
     let output_path = Path::new(output_dir);
     if !output_path.is_dir() {
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
-            "Directorio de salida no existe",
-        ));
+        return Err(io::Error::other("Directorio de salida no existe"));
     }
 
     let output_file_name = input_file;
     let output_file_path = output_path.join(output_file_name);
     let mut file = File::create(&output_file_path)?;
+    escribir_tablero(&mut file, tablero)
+}
 
-    for row in &tablero.cuadricula {
-        esribir_linea_en_archivo(&mut file, row)?;
+/// Escribe el contenido de un tablero, con el mismo formato que los archivos de entrada, en
+/// cualquier destino que implemente `Write`.
+///
+/// # Argumentos
+///
+/// * `destino`: Donde se escribe el tablero (un archivo, la salida estándar, un búfer, etc.).
+/// * `tablero`: Una referencia al tablero que se va a escribir.
+///
+pub fn escribir_tablero<W: Write>(destino: &mut W, tablero: &Tablero) -> Result<(), io::Error> {
+    if tablero.toroidal {
+        writeln!(destino, "{}", DIRECTIVA_TOROIDAL)?;
+    }
+    for (y, row) in tablero.cuadricula.iter().enumerate() {
+        esribir_linea_en_archivo(destino, tablero, y, row.len())?;
     }
-
     Ok(())
 }
 
-fn esribir_linea_en_archivo(file: &mut File, row: &[Objeto]) -> Result<(), io::Error> {
-    for objeto in row {
-        let simbolo = match objeto {
-            Objeto::Enemigo(vida, _) => format!("F{}", vida),
-            Objeto::Bomba(false, alcance) => format!("B{}", alcance),
-            Objeto::Bomba(true, alcance) => format!("S{}", alcance),
-            Objeto::Roca => "R".to_string(),
-            Objeto::Pared => "W".to_string(),
-            Objeto::Desvio(direccion) => match direccion {
-                Direccion::Arriba => "DU".to_string(),
-                Direccion::Abajo => "DD".to_string(),
-                Direccion::Izquierda => "DL".to_string(),
-                Direccion::Derecha => "DR".to_string(),
-            },
-            Objeto::Vacio => "_".to_string(),
-        };
+fn esribir_linea_en_archivo<W: Write>(
+    file: &mut W,
+    tablero: &Tablero,
+    y: usize,
+    columnas: usize,
+) -> Result<(), io::Error> {
+    for x in 0..columnas {
+        let simbolo = tablero.simbolo_en(x, y).unwrap_or_default();
         write!(file, "{} ", simbolo)?;
     }
     writeln!(file)?;
@@ -262,6 +596,7 @@ fn esribir_linea_en_archivo(file: &mut File, row: &[Objeto]) -> Result<(), io::E
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tablero::model::direccion::Direcciones;
     use std::collections::HashSet;
 
     #[test]
@@ -277,11 +612,15 @@ mod tests {
         tablero.cuadricula = vec![
             vec![Objeto::Vacio, Objeto::Roca, Objeto::Pared],
             vec![
-                Objeto::Bomba(false, 2),
-                Objeto::Desvio(Direccion::Arriba),
-                Objeto::Enemigo(3, HashSet::new()),
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                Objeto::Desvio(Direccion::Arriba, None),
+                Objeto::Enemigo(3, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![
+                Objeto::Bomba(true, 1, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+                Objeto::Roca,
             ],
-            vec![Objeto::Bomba(true, 1), Objeto::Vacio, Objeto::Roca],
         ];
 
         assert_eq!(
@@ -290,7 +629,7 @@ mod tests {
         );
         assert_eq!(
             tablero.obtener_objeto_en_posicion(1, 1),
-            Some(&Objeto::Desvio(Direccion::Arriba))
+            Some(&Objeto::Desvio(Direccion::Arriba, None))
         );
         assert_eq!(
             tablero.obtener_objeto_en_posicion(2, 2),
@@ -304,12 +643,20 @@ mod tests {
     fn test_detonar() {
         let mut tablero = Tablero::new(3);
         tablero.cuadricula = vec![
-            vec![Objeto::Bomba(false, 1), Objeto::Vacio, Objeto::Vacio],
-            vec![Objeto::Vacio, Objeto::Bomba(true, 2), Objeto::Vacio],
             vec![
-                Objeto::Enemigo(2, HashSet::new()),
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+                Objeto::Vacio,
+            ],
+            vec![
+                Objeto::Vacio,
+                Objeto::Bomba(true, 2, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+            ],
+            vec![
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
                 Objeto::Roca,
-                Objeto::Bomba(false, 1),
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
             ],
         ];
 
@@ -351,14 +698,18 @@ mod tests {
         tablero.cuadricula = vec![
             vec![
                 Objeto::Vacio,
-                Objeto::Enemigo(3, HashSet::new()),
-                Objeto::Bomba(false, 2),
+                Objeto::Enemigo(3, HashSet::new(), TipoDeEnemigo::Comun),
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+            ],
+            vec![
+                Objeto::Bomba(true, 1, Direcciones::ORTOGONALES),
+                Objeto::Roca,
+                Objeto::Pared,
             ],
-            vec![Objeto::Bomba(true, 1), Objeto::Roca, Objeto::Pared],
             vec![
-                Objeto::Desvio(Direccion::Derecha),
+                Objeto::Desvio(Direccion::Derecha, None),
                 Objeto::Vacio,
-                Objeto::Bomba(false, 3),
+                Objeto::Bomba(false, 3, Direcciones::ORTOGONALES),
             ],
         ];
 
@@ -367,12 +718,21 @@ mod tests {
 
         let resultado1 = tablero.detonar_en_posicion(1, 0, false, 0, 0);
         assert!(resultado1);
-        assert_eq!(tablero.cuadricula[0][1], Objeto::Enemigo(2, set));
+        assert_eq!(
+            tablero.cuadricula[0][1],
+            Objeto::Enemigo(2, set, TipoDeEnemigo::Comun)
+        );
 
         let resultado2 = tablero.detonar_en_posicion(0, 1, true, 1, 1);
         assert!(resultado2);
-        assert_eq!(tablero.cuadricula[0][2], Objeto::Bomba(false, 2));
-        assert_eq!(tablero.cuadricula[2][0], Objeto::Desvio(Direccion::Derecha));
+        assert_eq!(
+            tablero.cuadricula[0][2],
+            Objeto::Bomba(false, 2, Direcciones::ORTOGONALES)
+        );
+        assert_eq!(
+            tablero.cuadricula[2][0],
+            Objeto::Desvio(Direccion::Derecha, None)
+        );
 
         let resultado3 = tablero.detonar_en_posicion(2, 2, false, 2, 0);
         assert!(resultado3);
@@ -384,16 +744,20 @@ mod tests {
     fn test_detonar_bomba_con_diferente_alcance() {
         let mut tablero = Tablero::new(3);
         tablero.cuadricula = vec![
-            vec![Objeto::Bomba(false, 2), Objeto::Vacio, Objeto::Vacio],
             vec![
-                Objeto::Bomba(true, 1),
-                Objeto::Bomba(false, 3),
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+                Objeto::Vacio,
+            ],
+            vec![
+                Objeto::Bomba(true, 1, Direcciones::ORTOGONALES),
+                Objeto::Bomba(false, 3, Direcciones::ORTOGONALES),
                 Objeto::Vacio,
             ],
             vec![
-                Objeto::Bomba(true, 1),
+                Objeto::Bomba(true, 1, Direcciones::ORTOGONALES),
                 Objeto::Vacio,
-                Objeto::Bomba(false, 4),
+                Objeto::Bomba(false, 4, Direcciones::ORTOGONALES),
             ],
         ];
 
@@ -424,30 +788,43 @@ mod tests {
     fn test_detonar_in_direction() {
         let mut tablero = Tablero::new(3);
         tablero.cuadricula = vec![
-            vec![Objeto::Bomba(false, 2), Objeto::Vacio, Objeto::Vacio],
             vec![
-                Objeto::Bomba(true, 2),
-                Objeto::Enemigo(3, HashSet::new()),
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+                Objeto::Vacio,
+            ],
+            vec![
+                Objeto::Bomba(true, 2, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(3, HashSet::new(), TipoDeEnemigo::Comun),
                 Objeto::Roca,
             ],
             vec![
-                Objeto::Desvio(Direccion::Derecha),
-                Objeto::Enemigo(2, HashSet::new()),
-                Objeto::Bomba(false, 2),
+                Objeto::Desvio(Direccion::Derecha, None),
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
             ],
         ];
 
         tablero.detonar_en_direccion((0, 1, 0, 1, true), Direccion::Derecha, 2);
         let mut set_uno = HashSet::new();
         set_uno.insert((0, 1));
-        assert_eq!(tablero.cuadricula[1][1], Objeto::Enemigo(2, set_uno));
+        assert_eq!(
+            tablero.cuadricula[1][1],
+            Objeto::Enemigo(2, set_uno, TipoDeEnemigo::Comun)
+        );
         assert_eq!(tablero.cuadricula[1][2], Objeto::Roca);
 
         tablero.detonar_en_direccion((0, 1, 0, 1, true), Direccion::Abajo, 2);
         let mut set_dos = HashSet::new();
         set_dos.insert((0, 1));
-        assert_eq!(tablero.cuadricula[2][0], Objeto::Desvio(Direccion::Derecha));
-        assert_eq!(tablero.cuadricula[2][1], Objeto::Enemigo(1, set_dos));
+        assert_eq!(
+            tablero.cuadricula[2][0],
+            Objeto::Desvio(Direccion::Derecha, None)
+        );
+        assert_eq!(
+            tablero.cuadricula[2][1],
+            Objeto::Enemigo(1, set_dos, TipoDeEnemigo::Comun)
+        );
     }
 
     #[test]
@@ -462,13 +839,13 @@ mod tests {
         let mut tablero = Tablero::new(3);
         tablero.cuadricula = vec![
             vec![
-                Objeto::Bomba(false, 3),
-                Objeto::Enemigo(2, HashSet::new()),
+                Objeto::Bomba(false, 3, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
                 Objeto::Vacio,
             ],
             vec![
-                Objeto::Desvio(Direccion::Derecha),
-                Objeto::Desvio(Direccion::Arriba),
+                Objeto::Desvio(Direccion::Derecha, None),
+                Objeto::Desvio(Direccion::Arriba, None),
                 Objeto::Roca,
             ],
             vec![Objeto::Pared, Objeto::Vacio, Objeto::Pared],
@@ -483,7 +860,10 @@ mod tests {
         );
         let mut set = HashSet::new();
         set.insert((0, 0));
-        assert_eq!(tablero.cuadricula[0][1], Objeto::Enemigo(1, set));
+        assert_eq!(
+            tablero.cuadricula[0][1],
+            Objeto::Enemigo(1, set, TipoDeEnemigo::Comun)
+        );
     }
 
     #[test]
@@ -498,10 +878,10 @@ mod tests {
                 Objeto::Vacio,
             ],
             vec![
-                Objeto::Bomba(false, 1),
-                Objeto::Bomba(false, 2),
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
                 Objeto::Pared,
-                Objeto::Enemigo(1, HashSet::new()),
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
             ],
             vec![
                 Objeto::Vacio,
@@ -538,7 +918,369 @@ mod tests {
         assert_eq!(tablero.cuadricula[1][1], Objeto::Vacio); // Bomba explotó, pero detenida por la pared.
         assert_eq!(tablero.cuadricula[1][2], Objeto::Pared); // La pared detuvo la explosión.
         let set = HashSet::new();
-        assert_eq!(tablero.cuadricula[1][3], Objeto::Enemigo(1, set));
+        assert_eq!(
+            tablero.cuadricula[1][3],
+            Objeto::Enemigo(1, set, TipoDeEnemigo::Comun)
+        );
+    }
+
+    #[test]
+    fn test_detonar_en_diagonal() {
+        let mut tablero = leer_tablero("W F1 F1\n_ B2:X _\nW _ F1\n".as_bytes()).unwrap();
+
+        assert!(tablero.detonar(1, 1).is_ok());
+        // Caen los enemigos de las diagonales; el de la cruz no se ve afectado.
+        assert_eq!(tablero.cuadricula[0][2], Objeto::Vacio);
+        assert_eq!(tablero.cuadricula[2][2], Objeto::Vacio);
+        assert_eq!(
+            tablero.cuadricula[0][1],
+            Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun)
+        );
+        // Las paredes detienen los rayos diagonales igual que a los ortogonales.
+        assert_eq!(tablero.cuadricula[0][0], Objeto::Pared);
+        assert_eq!(tablero.cuadricula[2][0], Objeto::Pared);
+    }
+
+    #[test]
+    fn test_detonar_en_ocho_direcciones() {
+        let mut tablero = leer_tablero("F1 F1 F1\nF1 B1:* F1\nF1 F1 F1\n".as_bytes()).unwrap();
+
+        assert!(tablero.detonar(1, 1).is_ok());
+        assert_eq!(tablero.cantidad_enemigos(), 0);
+    }
+
+    #[test]
+    fn test_desvio_diagonal() {
+        let mut tablero = leer_tablero("B2 DDR _\n_ _ F1\n_ _ _\n".as_bytes()).unwrap();
+
+        // El rayo hacia la derecha llega al desvío y sigue en diagonal hacia abajo.
+        assert!(tablero.detonar(0, 0).is_ok());
+        assert_eq!(tablero.cuadricula[1][2], Objeto::Vacio);
+    }
+
+    #[test]
+    fn test_detonar_bombas_de_linea() {
+        let texto = "_ F1 _\nF1 B1:H F1\n_ F1 _\n";
+        let mut horizontal = leer_tablero(texto.as_bytes()).unwrap();
+        assert!(horizontal.detonar(1, 1).is_ok());
+        assert_eq!(horizontal.cantidad_enemigos(), 2);
+        assert_eq!(horizontal.cuadricula[1][0], Objeto::Vacio);
+        assert_eq!(horizontal.cuadricula[1][2], Objeto::Vacio);
+
+        let mut hacia_arriba = leer_tablero(texto.replace("B1:H", "B1:U").as_bytes()).unwrap();
+        assert!(hacia_arriba.detonar(1, 1).is_ok());
+        assert_eq!(hacia_arriba.cantidad_enemigos(), 3);
+        assert_eq!(hacia_arriba.cuadricula[0][1], Objeto::Vacio);
+    }
+
+    #[test]
+    fn test_espejos_reflejan_segun_la_direccion_de_llegada() {
+        let texto = "F1 _ _ _\nB3 _ \\ _\n_ _ _ _\nF1 _ F1 _\n";
+        let mut tablero = leer_tablero(texto.as_bytes()).unwrap();
+        let mut barra = leer_tablero(texto.replace('\\', "/").as_bytes()).unwrap();
+
+        // El rayo hacia la derecha llega al espejo y sale hacia abajo con el alcance que le
+        // queda, que no llega hasta el enemigo de (2, 3); no sigue de largo hacia (3, 1).
+        assert!(tablero.detonar(0, 1).is_ok());
+        assert_eq!(
+            tablero.cuadricula[3][2],
+            Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun)
+        );
+        assert!(tablero.eventos.contains(&Evento::Alcance(2, 2, (0, 1))));
+        assert!(!tablero.eventos.contains(&Evento::Alcance(3, 1, (0, 1))));
+
+        // Con el espejo `/` el mismo rayo sale hacia arriba.
+        assert!(barra.detonar(0, 1).is_ok());
+        assert!(barra.eventos.contains(&Evento::Alcance(2, 0, (0, 1))));
+        assert!(!barra.eventos.contains(&Evento::Alcance(2, 2, (0, 1))));
+    }
+
+    #[test]
+    fn test_divisor_parte_el_rayo() {
+        let mut tablero =
+            leer_tablero("_ _ F1 _\nB4 _ Y F1\n_ _ W _\n_ _ F1 _\n".as_bytes()).unwrap();
+
+        assert!(tablero.detonar(0, 1).is_ok());
+        // La rama de arriba llega al enemigo; la de abajo se detiene en la pared y el rayo
+        // original no sigue después del divisor.
+        assert_eq!(tablero.cuadricula[0][2], Objeto::Vacio);
+        assert_eq!(
+            tablero.cuadricula[3][2],
+            Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun)
+        );
+        assert_eq!(
+            tablero.cuadricula[1][3],
+            Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun)
+        );
+    }
+
+    #[test]
+    fn test_divisor_golpea_una_vez_por_bomba() {
+        let texto = "_ _ _ _ _\n_ DR _ DD _\nB6 Y _ F2 _\n_ DR _ DU _\n_ _ _ _ _\n";
+        let mut tablero = leer_tablero(texto.as_bytes()).unwrap();
+
+        // Las dos ramas llegan al enemigo por distintos desvíos, pero cuentan como una sola
+        // bomba.
+        assert!(tablero.detonar(0, 2).is_ok());
+        let impactos = tablero
+            .eventos
+            .iter()
+            .filter(|evento| matches!(evento, Evento::Impacto(3, 2, _, _)))
+            .count();
+        assert_eq!(impactos, 1);
+        assert_eq!(
+            tablero.cuadricula[2][3],
+            Objeto::Enemigo(1, [(0, 2)].into_iter().collect(), TipoDeEnemigo::Comun)
+        );
+        assert!(tablero.eventos.contains(&Evento::Alcance(3, 3, (0, 2))));
+    }
+
+    #[test]
+    fn test_enemigo_blindado() {
+        let mut tablero = leer_tablero("B1 F1:A S1\n_ _ _\n_ _ _\n".as_bytes()).unwrap();
+
+        // La bomba común lo alcanza pero no le quita vida; la de traspaso sí.
+        assert!(tablero.detonar(0, 0).is_ok());
+        assert!(tablero.eventos.contains(&Evento::Alcance(1, 0, (0, 0))));
+        assert_eq!(
+            tablero.cuadricula[0][1],
+            Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Blindado)
+        );
+        assert!(tablero.detonar(2, 0).is_ok());
+        assert_eq!(tablero.cuadricula[0][1], Objeto::Vacio);
+    }
+
+    #[test]
+    fn test_enemigo_vulnerable_recibe_cada_golpe() {
+        let texto = "_ _ _ _ _\n_ DR _ DD _\nB6 Y _ F2:V _\n_ DR _ DU _\n_ _ _ _ _\n";
+        let mut tablero = leer_tablero(texto.as_bytes()).unwrap();
+
+        // Las dos ramas del divisor vienen de la misma bomba, pero cada una le quita una vida.
+        assert!(tablero.detonar(0, 2).is_ok());
+        assert_eq!(tablero.cuadricula[2][3], Objeto::Vacio);
+    }
+
+    #[test]
+    fn test_enemigo_explosivo() {
+        let mut tablero = leer_tablero("B1 F1:E1 F1\n_ _ _\n_ _ F1\n".as_bytes()).unwrap();
+
+        assert!(tablero.detonar(0, 0).is_ok());
+        // Al morir explota como una bomba de alcance 1 y elimina al enemigo de al lado.
+        assert!(tablero.eventos.contains(&Evento::Detonacion(1, 0)));
+        assert_eq!(tablero.cuadricula[0][1], Objeto::Vacio);
+        assert_eq!(tablero.cuadricula[0][2], Objeto::Vacio);
+        assert_eq!(tablero.cantidad_enemigos(), 1);
+    }
+
+    #[test]
+    fn test_interruptor_abre_compuerta_para_los_rayos_siguientes() {
+        // El rayo hacia abajo se procesa antes que el rayo hacia la derecha, así que cuando
+        // este llega a la compuerta ya está abierta.
+        let mut tablero = leer_tablero("B2 C1 F1\nI1 _ _\n_ _ _\n".as_bytes()).unwrap();
+        assert!(tablero.detonar(0, 0).is_ok());
+        assert_eq!(tablero.cuadricula[0][1], Objeto::Compuerta(1, true));
+        assert_eq!(tablero.cuadricula[0][2], Objeto::Vacio);
+        assert!(tablero.eventos.contains(&Evento::Conmutacion(1, 0, (0, 1))));
+
+        // Si el interruptor se activa después, la compuerta ya detuvo al rayo.
+        let mut tablero = leer_tablero("B2 I1 _\nC1 _ _\nF1 _ _\n".as_bytes()).unwrap();
+        assert!(tablero.detonar(0, 0).is_ok());
+        assert_eq!(tablero.cuadricula[1][0], Objeto::Compuerta(1, true));
+        assert_eq!(
+            tablero.cuadricula[2][0],
+            Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun)
+        );
+    }
+
+    #[test]
+    fn test_interruptor_gira_desvio() {
+        let mut tablero = leer_tablero("I2 _ _\nB2 DR:2 _\n_ F1 _\n".as_bytes()).unwrap();
+
+        assert!(tablero.detonar(0, 1).is_ok());
+        assert_eq!(
+            tablero.cuadricula[1][1],
+            Objeto::Desvio(Direccion::Abajo, Some(2))
+        );
+        assert_eq!(tablero.cuadricula[2][1], Objeto::Vacio);
+    }
+
+    #[test]
+    fn test_rayos_dan_la_vuelta_en_tablero_toroidal() {
+        let texto = "F1 _ B1\n_ _ _\n_ _ F1\n";
+        let mut plano = leer_tablero(texto.as_bytes()).unwrap();
+        assert!(plano.detonar(2, 0).is_ok());
+        assert_eq!(plano.cantidad_enemigos(), 2);
+
+        // Hacia la derecha vuelve a entrar por (0, 0) y hacia arriba por (2, 2).
+        let mut toroidal = leer_tablero(format!("@toroidal\n{}", texto).as_bytes()).unwrap();
+        assert!(toroidal.detonar(2, 0).is_ok());
+        assert_eq!(toroidal.cantidad_enemigos(), 0);
+    }
+
+    #[test]
+    fn test_bomba_de_area_en_tablero_toroidal() {
+        let texto = "AC1 _ F1\n_ _ _\nF1 _ F2:V\n";
+        let mut plano = leer_tablero(texto.as_bytes()).unwrap();
+        assert!(plano.detonar(0, 0).is_ok());
+        assert_eq!(plano.cantidad_enemigos(), 3);
+
+        // Las celdas de la columna y la fila opuestas quedan a distancia 1 dando la vuelta.
+        // El enemigo vulnerable de (2, 2) recibe un solo golpe aunque varios desplazamientos
+        // del cuadrado caigan en su celda.
+        let mut toroidal = leer_tablero(format!("@toroidal\n{}", texto).as_bytes()).unwrap();
+        assert!(toroidal.detonar(0, 0).is_ok());
+        assert_eq!(toroidal.cantidad_enemigos(), 1);
+        assert_eq!(
+            toroidal.cuadricula[2][2],
+            Objeto::Enemigo(1, [(0, 0)].into_iter().collect(), TipoDeEnemigo::Vulnerable)
+        );
+    }
+
+    #[test]
+    fn test_bomba_de_area_ocluida_en_tablero_toroidal() {
+        let texto = "@toroidal\nAR2 _ _ F1 W\n_ _ _ _ _\n_ _ _ _ _\n_ _ _ _ _\n_ _ _ _ _\n";
+        // El enemigo de (3, 0) queda a distancia 2 dando la vuelta, detrás de la pared de
+        // (4, 0).
+        let mut ocluida = leer_tablero(texto.as_bytes()).unwrap();
+        assert!(ocluida.detonar(0, 0).is_ok());
+        assert_eq!(ocluida.cantidad_enemigos(), 1);
+
+        let mut sin_pared = leer_tablero(texto.replace(" W", " _").as_bytes()).unwrap();
+        assert!(sin_pared.detonar(0, 0).is_ok());
+        assert_eq!(sin_pared.cantidad_enemigos(), 0);
+    }
+
+    #[test]
+    fn test_rayo_toroidal_no_vuelve_a_su_bomba() {
+        let mut tablero =
+            leer_tablero("@toroidal\nB5:R _ F2:V\n_ _ _\n_ _ _\n".as_bytes()).unwrap();
+
+        // Con alcance 5 el rayo volvería a pasar por el enemigo vulnerable, pero se corta al
+        // llegar de nuevo a la celda de la bomba.
+        assert!(tablero.detonar(0, 0).is_ok());
+        assert_eq!(
+            tablero.cuadricula[0][2],
+            Objeto::Enemigo(1, [(0, 0)].into_iter().collect(), TipoDeEnemigo::Vulnerable)
+        );
+        assert_eq!(tablero.eventos.len(), 4);
+    }
+
+    #[test]
+    fn test_bomba_sobre_desvio_deja_el_desvio() {
+        let mut tablero = leer_tablero("B1 B1+DD _\n_ _ _\n_ F1 _\n".as_bytes()).unwrap();
+
+        // El rayo detona la bomba de arriba del desvío; el desvío queda solo en la celda y
+        // sigue guiando los rayos de las detonaciones siguientes.
+        assert!(tablero.detonar(0, 0).is_ok());
+        assert_eq!(tablero.simbolo_en(1, 0), Some("DD".to_string()));
+        assert_eq!(*tablero.suelo_en(1, 0), Objeto::Vacio);
+        assert_eq!(
+            tablero.cuadricula[2][1],
+            Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun)
+        );
+
+        tablero.cuadricula[0][0] = Objeto::Bomba(false, 3, Direcciones::ORTOGONALES);
+        assert!(tablero.detonar(0, 0).is_ok());
+        assert_eq!(tablero.cuadricula[2][1], Objeto::Vacio);
+    }
+
+    #[test]
+    fn test_enemigo_sobre_espejo_recibe_el_rayo_antes_de_reflejarlo() {
+        let mut tablero = leer_tablero("B3 F1+\\ _\n_ _ _\n_ F1 _\n".as_bytes()).unwrap();
+        assert!(tablero.detonar(0, 0).is_ok());
+        assert_eq!(
+            tablero.cuadricula[0][1],
+            Objeto::Espejo(model::espejo::Espejo::BarraInvertida)
+        );
+        assert_eq!(tablero.cuadricula[2][1], Objeto::Vacio);
+    }
+
+    #[test]
+    fn test_roca_sobre_desvio_solo_deja_pasar_rayos_de_traspaso() {
+        let contenido = "{} R+DD _\n_ _ _\n_ F1 _\n";
+        let mut tablero = leer_tablero(contenido.replace("{}", "B3").as_bytes()).unwrap();
+        assert!(tablero.detonar(0, 0).is_ok());
+        assert_eq!(tablero.cantidad_enemigos(), 1);
+
+        let mut tablero = leer_tablero(contenido.replace("{}", "S3").as_bytes()).unwrap();
+        assert!(tablero.detonar(0, 0).is_ok());
+        assert_eq!(tablero.cantidad_enemigos(), 0);
+        assert_eq!(tablero.cuadricula[0][1], Objeto::Roca);
+    }
+
+    #[test]
+    fn test_interruptor_debajo_de_un_enemigo() {
+        let mut tablero = leer_tablero("B1 F2+I1 C1\n_ _ _\n_ _ _\n".as_bytes()).unwrap();
+        assert!(tablero.detonar(0, 0).is_ok());
+        assert_eq!(
+            tablero.cuadricula[0][1],
+            Objeto::Enemigo(1, [(0, 0)].into_iter().collect(), TipoDeEnemigo::Comun)
+        );
+        assert_eq!(tablero.cuadricula[0][2], Objeto::Compuerta(1, true));
+    }
+
+    #[test]
+    fn test_detonar_bomba_de_area() {
+        let texto = "F1 F1 F1 _ F1\nF1 AC1 F1 _ _\nF1 R F1 _ _\n_ _ _ _ _\n_ _ _ _ _\n";
+        let mut cuadrado = leer_tablero(texto.as_bytes()).unwrap();
+        assert!(cuadrado.detonar(1, 1).is_ok());
+        // Cae todo el cuadrado alrededor de la bomba; la roca no detiene la explosión.
+        assert_eq!(cuadrado.cantidad_enemigos(), 1);
+        assert_eq!(cuadrado.cuadricula[2][1], Objeto::Roca);
+
+        let mut rombo = leer_tablero(texto.replace("AC1", "AR1").as_bytes()).unwrap();
+        assert!(rombo.detonar(1, 1).is_ok());
+        assert_eq!(rombo.cantidad_enemigos(), 5);
+        assert_eq!(rombo.cuadricula[0][1], Objeto::Vacio);
+        assert_eq!(
+            rombo.cuadricula[0][0],
+            Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun)
+        );
+    }
+
+    #[test]
+    fn test_bomba_de_area_ocluida_por_paredes() {
+        let texto = "AR2 W F1\n_ _ _\nF1 _ _\n";
+        let mut ocluida = leer_tablero(texto.as_bytes()).unwrap();
+        assert!(ocluida.detonar(0, 0).is_ok());
+        // La pared tapa al enemigo de (2, 0), pero no al de (0, 2).
+        assert_eq!(
+            ocluida.cuadricula[0][2],
+            Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun)
+        );
+        assert_eq!(ocluida.cuadricula[2][0], Objeto::Vacio);
+        assert_eq!(ocluida.cuadricula[0][1], Objeto::Pared);
+
+        let mut sin_oclusion = leer_tablero(texto.replace("AR2", "AR2:T").as_bytes()).unwrap();
+        assert!(sin_oclusion.detonar(0, 0).is_ok());
+        assert_eq!(sin_oclusion.cantidad_enemigos(), 0);
+    }
+
+    #[test]
+    fn test_bomba_de_area_con_alcance_enorme() {
+        let texto = "AC2000000000 _ F1\n_ _ _\nF1 _ AR2147483647\n";
+        let mut tablero = leer_tablero(texto.as_bytes()).unwrap();
+
+        assert!(tablero.detonar(0, 0).is_ok());
+        assert_eq!(tablero.cantidad_enemigos(), 0);
+        assert_eq!(tablero.cuadricula[2][2], Objeto::Vacio);
+
+        // Solo el rombo llega a la esquina opuesta, a distancia 4 en un tablero de 3x3.
+        for simbolo in ["AR5", "AR2147483647"] {
+            let texto = format!("{} _ _\n_ _ _\n_ _ F1\n", simbolo);
+            let mut rombo = leer_tablero(texto.as_bytes()).unwrap();
+            assert!(rombo.detonar(0, 0).is_ok());
+            assert_eq!(rombo.cuadricula[2][2], Objeto::Vacio);
+        }
+    }
+
+    #[test]
+    fn test_bomba_de_area_en_cadena() {
+        let mut tablero = leer_tablero("B1 AC1 _\n_ _ F1\n_ _ _\n".as_bytes()).unwrap();
+
+        assert!(tablero.detonar(0, 0).is_ok());
+        assert_eq!(tablero.cuadricula[0][1], Objeto::Vacio);
+        assert_eq!(tablero.cuadricula[1][2], Objeto::Vacio);
     }
 
     use std::fs::{self, File};
@@ -557,7 +1299,7 @@ mod tests {
         let content = "F2 R\nW DU";
         let file_path = "test_maze.txt";
         if let Err(e) = create_test_file(file_path, content) {
-            assert!(false, "Failed to create test file: {:?}", e);
+            panic!("Failed to create test file: {:?}", e);
         }
 
         // Prueba para crear un tablero a partir del archivo de prueba.
@@ -578,7 +1320,7 @@ mod tests {
 
         // Limpiar el archivo de prueba después de usarlo.
         if let Err(e) = fs::remove_file(file_path) {
-            assert!(false, "Failed to remove test file: {:?}", e);
+            panic!("Failed to remove test file: {:?}", e);
         }
     }
 
@@ -587,7 +1329,10 @@ mod tests {
         // Crear un tablero de prueba.
         let mut tablero = Tablero::new(2);
         tablero.cuadricula = vec![
-            vec![Objeto::Bomba(false, 1), Objeto::Enemigo(2, HashSet::new())],
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
             vec![Objeto::Roca, Objeto::Pared],
         ];
 
@@ -613,33 +1358,128 @@ mod tests {
                 "El contenido del archivo no coincide"
             );
         } else {
-            assert!(false, "Failed to read output file");
+            panic!("Failed to read output file");
         }
 
         // Eliminar el archivo temporal después de usarlo.
         if let Err(e) = fs::remove_file(output_file_path) {
-            assert!(false, "Failed to remove output file: {:?}", e);
+            panic!("Failed to remove output file: {:?}", e);
+        }
+    }
+
+    #[test]
+    fn test_escribir_tablero() {
+        let mut tablero = Tablero::new(2);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(true, 3, Direcciones::ORTOGONALES),
+                Objeto::Desvio(Direccion::Izquierda, None),
+            ],
+            vec![
+                Objeto::Vacio,
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+        ];
+
+        let mut destino = Vec::new();
+        assert!(escribir_tablero(&mut destino, &tablero).is_ok());
+        assert_eq!(String::from_utf8_lossy(&destino), "S3 DL \n_ F1 \n");
+    }
+
+    #[test]
+    fn test_leer_y_escribir_tablero_toroidal() {
+        let tablero = leer_tablero("@toroidal\nB1 _\n_ F1\n".as_bytes()).unwrap();
+        assert!(tablero.toroidal);
+        assert_eq!(tablero.cuadricula.len(), 2);
+
+        let mut destino = Vec::new();
+        assert!(escribir_tablero(&mut destino, &tablero).is_ok());
+        assert_eq!(
+            String::from_utf8_lossy(&destino),
+            "@toroidal\nB1 _ \n_ F1 \n"
+        );
+        assert_eq!(leer_tablero(destino.as_slice()).unwrap(), tablero);
+    }
+
+    #[test]
+    fn test_leer_y_escribir_tablero_con_suelo() {
+        let contenido = "B2+DR _ _ \n_ F1:A+/ _ \nI1 _ C1 \n";
+        let tablero = leer_tablero(contenido.as_bytes()).unwrap();
+        assert_eq!(
+            tablero.cuadricula[0][0],
+            Objeto::Bomba(false, 2, Direcciones::ORTOGONALES)
+        );
+        assert_eq!(
+            *tablero.suelo_en(0, 0),
+            Objeto::Desvio(Direccion::Derecha, None)
+        );
+        assert_eq!(*tablero.suelo_en(2, 2), Objeto::Vacio);
+
+        let mut destino = Vec::new();
+        assert!(escribir_tablero(&mut destino, &tablero).is_ok());
+        assert_eq!(String::from_utf8_lossy(&destino), contenido);
+
+        assert!(leer_tablero("B2 _\n_ _\n".as_bytes())
+            .unwrap()
+            .suelo
+            .is_empty());
+        // Una baldosa sin ocupante se escribe sola.
+        assert_eq!(
+            leer_tablero("_+I1 _\n_ _\n".as_bytes()).unwrap(),
+            leer_tablero("I1 _\n_ _\n".as_bytes()).unwrap()
+        );
+        assert_eq!(
+            leer_tablero("W+DR _\n_ _\n".as_bytes())
+                .map(|_| ())
+                .map_err(|e| e.kind()),
+            Err(io::ErrorKind::InvalidData)
+        );
+    }
+
+    #[test]
+    fn test_crear_tablero_con_simbolo_invalido() {
+        let file_path = "test_maze_invalido.txt";
+        if let Err(e) = create_test_file(file_path, "B1 X\n_ _") {
+            panic!("Failed to create test file: {:?}", e);
+        }
+
+        let resultado = crear_tablero(file_path);
+        assert_eq!(
+            resultado.map(|_| ()).map_err(|e| e.kind()),
+            Err(io::ErrorKind::InvalidData)
+        );
+
+        if let Err(e) = fs::remove_file(file_path) {
+            panic!("Failed to remove test file: {:?}", e);
         }
     }
 
+    #[test]
+    fn test_leer_tablero_con_portal_sin_pareja() {
+        let resultado = leer_tablero("P1 _\nP2 P1".as_bytes());
+        let error = resultado.map(|_| ()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), "(0, 1): El portal P2 no tiene pareja");
+    }
+
     #[test]
     fn test_detonar_en_posicion_no_es_bomba() {
         let mut tablero = Tablero::new(3);
         tablero.cuadricula = vec![
             vec![
                 Objeto::Vacio,
-                Objeto::Enemigo(3, HashSet::new()),
+                Objeto::Enemigo(3, HashSet::new(), TipoDeEnemigo::Comun),
                 Objeto::Roca,
             ],
             vec![
-                Objeto::Desvio(Direccion::Derecha),
+                Objeto::Desvio(Direccion::Derecha, None),
                 Objeto::Vacio,
-                Objeto::Enemigo(2, HashSet::new()),
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
             ],
             vec![
-                Objeto::Bomba(true, 2),
+                Objeto::Bomba(true, 2, Direcciones::ORTOGONALES),
                 Objeto::Pared,
-                Objeto::Bomba(false, 2),
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
             ],
         ];
 
@@ -647,7 +1487,10 @@ mod tests {
         let result = tablero.detonar(0, 1);
         assert!(result.is_err(), "Se esperaba un error al detonar en (0, 1)");
         assert_eq!(tablero.cuadricula[0][0], Objeto::Vacio);
-        assert_eq!(tablero.cuadricula[0][1], Objeto::Enemigo(3, HashSet::new()));
+        assert_eq!(
+            tablero.cuadricula[0][1],
+            Objeto::Enemigo(3, HashSet::new(), TipoDeEnemigo::Comun)
+        );
         assert_eq!(tablero.cuadricula[0][2], Objeto::Roca);
     }
 
@@ -656,16 +1499,20 @@ mod tests {
         let mut tablero = Tablero::new(3);
         tablero.cuadricula = vec![
             vec![
-                Objeto::Bomba(true, 2),
+                Objeto::Bomba(true, 2, Direcciones::ORTOGONALES),
                 Objeto::Roca,
-                Objeto::Enemigo(2, HashSet::new()),
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
             ],
             vec![
-                Objeto::Desvio(Direccion::Izquierda),
-                Objeto::Bomba(false, 1),
+                Objeto::Desvio(Direccion::Izquierda, None),
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
                 Objeto::Vacio,
             ],
-            vec![Objeto::Vacio, Objeto::Pared, Objeto::Bomba(true, 1)],
+            vec![
+                Objeto::Vacio,
+                Objeto::Pared,
+                Objeto::Bomba(true, 1, Direcciones::ORTOGONALES),
+            ],
         ];
 
         // Detonar fuera de los límites del tablero (3, 2).
@@ -676,6 +1523,9 @@ mod tests {
         );
         assert_eq!(tablero.cuadricula[2][0], Objeto::Vacio);
         assert_eq!(tablero.cuadricula[2][1], Objeto::Pared);
-        assert_eq!(tablero.cuadricula[2][2], Objeto::Bomba(true, 1));
+        assert_eq!(
+            tablero.cuadricula[2][2],
+            Objeto::Bomba(true, 1, Direcciones::ORTOGONALES)
+        );
     }
 }
@@ -0,0 +1,211 @@
+use super::model::evento::Evento;
+use super::Tablero;
+use std::collections::BTreeSet;
+
+/// Representa, para cada celda del tablero, el conjunto de bombas cuya explosión la alcanza.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapaDeCalor {
+    pub celdas: Vec<Vec<BTreeSet<(i32, i32)>>>,
+}
+
+impl MapaDeCalor {
+    /// Devuelve la cantidad de bombas que alcanzan la posición `(x, y)`, o 0 si está fuera
+    /// del tablero.
+    pub fn cantidad(&self, x: usize, y: usize) -> usize {
+        self.celdas
+            .get(y)
+            .and_then(|fila| fila.get(x))
+            .map_or(0, |bombas| bombas.len())
+    }
+
+    /// Dibuja el mapa como una grilla numérica con la cantidad de bombas por celda.
+    pub fn a_grilla(&self) -> String {
+        let ancho = self
+            .celdas
+            .iter()
+            .flatten()
+            .map(|bombas| bombas.len().to_string().len())
+            .max()
+            .unwrap_or(1);
+
+        let mut grilla = String::new();
+        for fila in &self.celdas {
+            let numeros: Vec<String> = fila
+                .iter()
+                .map(|bombas| format!("{:>ancho$}", bombas.len()))
+                .collect();
+            grilla.push_str(&numeros.join(" "));
+            grilla.push('\n');
+        }
+        grilla
+    }
+
+    /// Exporta el mapa en formato CSV con una línea por celda: sus coordenadas, la cantidad de
+    /// bombas que la alcanzan y la lista de esas bombas como `x:y` separadas por espacios.
+    pub fn a_csv(&self) -> String {
+        let mut csv = String::from("x,y,cantidad,bombas\n");
+        for (y, fila) in self.celdas.iter().enumerate() {
+            for (x, bombas) in fila.iter().enumerate() {
+                let lista: Vec<String> = bombas
+                    .iter()
+                    .map(|(bx, by)| format!("{}:{}", bx, by))
+                    .collect();
+                csv.push_str(&format!(
+                    "{},{},{},{}\n",
+                    x,
+                    y,
+                    bombas.len(),
+                    lista.join(" ")
+                ));
+            }
+        }
+        csv
+    }
+}
+
+impl Tablero {
+    /// Calcula el mapa de calor del tablero detonando cada bomba por separado sobre una copia
+    /// y registrando las celdas que alcanza su propia explosión (incluida la celda de la bomba).
+    ///
+    /// Las bombas que explotan en cadena aportan su alcance a su propia entrada, no a la de la
+    /// bomba que las activó.
+    pub fn mapa_de_calor(&self) -> MapaDeCalor {
+        let mut celdas: Vec<Vec<BTreeSet<(i32, i32)>>> = self
+            .cuadricula
+            .iter()
+            .map(|fila| vec![BTreeSet::new(); fila.len()])
+            .collect();
+
+        for bomba in self.posiciones_bombas() {
+            for (x, y) in self.celdas_alcanzadas(bomba) {
+                if let Some(celda) = celdas
+                    .get_mut(y as usize)
+                    .and_then(|fila| fila.get_mut(x as usize))
+                {
+                    celda.insert(bomba);
+                }
+            }
+        }
+
+        MapaDeCalor { celdas }
+    }
+
+    /// Devuelve las celdas que alcanza la explosión de la bomba ubicada en `bomba`, sin contar
+    /// las de las bombas que active en cadena.
+    pub fn celdas_alcanzadas(&self, bomba: (i32, i32)) -> BTreeSet<(i32, i32)> {
+        let mut simulacion = self.clone();
+        if simulacion.detonar(bomba.0, bomba.1).is_err() {
+            return BTreeSet::new();
+        }
+
+        simulacion
+            .eventos
+            .iter()
+            .filter_map(|evento| match evento {
+                Evento::Detonacion(x, y) if (*x, *y) == bomba => Some((*x, *y)),
+                Evento::Alcance(x, y, origen) if *origen == bomba => Some((*x, *y)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tablero::model::direccion::{Direccion, Direcciones};
+    use crate::tablero::model::enemigo::TipoDeEnemigo;
+    use crate::tablero::model::objeto::Objeto;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_celdas_alcanzadas_con_desvio_y_roca() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                Objeto::Roca,
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![
+                Objeto::Desvio(Direccion::Derecha, None),
+                Objeto::Vacio,
+                Objeto::Pared,
+            ],
+            vec![
+                Objeto::Vacio,
+                Objeto::Vacio,
+                Objeto::Bomba(true, 1, Direcciones::ORTOGONALES),
+            ],
+        ];
+
+        let alcanzadas = tablero.celdas_alcanzadas((0, 0));
+        let esperadas: BTreeSet<(i32, i32)> =
+            [(0, 0), (0, 1), (1, 1), (0, 2)].into_iter().collect();
+        assert_eq!(alcanzadas, esperadas);
+
+        let alcanzadas = tablero.celdas_alcanzadas((2, 2));
+        let esperadas: BTreeSet<(i32, i32)> = [(2, 2), (1, 2)].into_iter().collect();
+        assert_eq!(alcanzadas, esperadas);
+
+        assert!(tablero.celdas_alcanzadas((1, 1)).is_empty());
+    }
+
+    #[test]
+    fn test_mapa_de_calor() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                Objeto::Roca,
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![
+                Objeto::Desvio(Direccion::Derecha, None),
+                Objeto::Vacio,
+                Objeto::Pared,
+            ],
+            vec![
+                Objeto::Vacio,
+                Objeto::Vacio,
+                Objeto::Bomba(true, 1, Direcciones::ORTOGONALES),
+            ],
+        ];
+        let mapa = tablero.mapa_de_calor();
+
+        assert_eq!(mapa.cantidad(0, 0), 1);
+        assert_eq!(mapa.cantidad(1, 2), 1);
+        assert_eq!(mapa.cantidad(1, 0), 0);
+        assert_eq!(mapa.cantidad(5, 5), 0);
+        assert_eq!(mapa.a_grilla(), "1 0 0\n1 1 0\n1 1 1\n");
+        assert_eq!(
+            tablero.cuadricula[0][0],
+            Objeto::Bomba(false, 2, Direcciones::ORTOGONALES)
+        );
+    }
+
+    #[test]
+    fn test_mapa_de_calor_superpuesto() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+                Objeto::Vacio,
+            ],
+            vec![Objeto::Vacio, Objeto::Vacio, Objeto::Vacio],
+            vec![
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+                Objeto::Vacio,
+            ],
+        ];
+
+        let mapa = tablero.mapa_de_calor();
+        assert_eq!(mapa.a_grilla(), "2 1 1\n2 0 0\n2 1 1\n");
+
+        let csv = mapa.a_csv();
+        assert!(csv.starts_with("x,y,cantidad,bombas\n0,0,2,0:0 0:2\n"));
+        assert!(csv.contains("1,1,0,\n"));
+    }
+}
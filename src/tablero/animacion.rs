@@ -0,0 +1,334 @@
+use super::model::enemigo::TipoDeEnemigo;
+use super::model::evento::Evento;
+use super::model::objeto::Objeto;
+use super::Tablero;
+use std::collections::HashSet;
+use std::io::{self, BufRead, Write};
+use std::thread;
+use std::time::Duration;
+
+const LIMPIAR_PANTALLA: &str = "\x1b[2J\x1b[H";
+
+/// Representa un cuadro de la animación: el estado del tablero luego de un suceso y la
+/// descripción de ese suceso.
+#[derive(Debug, Clone)]
+pub struct Cuadro {
+    pub tablero: Tablero,
+    pub descripcion: String,
+}
+
+/// Indica cómo avanzar entre los cuadros de una animación.
+#[derive(PartialEq, Debug, Clone)]
+pub enum Ritmo {
+    /// Avanza automáticamente esperando la duración indicada entre cuadros.
+    Intervalo(Duration),
+    /// Avanza cada vez que el usuario presiona Enter.
+    Manual,
+}
+
+impl Tablero {
+    /// Detona la bomba de `(x, y)` sobre una copia del tablero y devuelve un cuadro por cada
+    /// suceso de la explosión: cada paso de los rayos, cada bomba encadenada y cada enemigo
+    /// golpeado. El primer cuadro muestra el tablero antes de la detonación.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve los cuadros de la animación, o `Err(String)` si en `(x, y)` no hay una bomba.
+    pub fn animar_detonacion(&self, x: i32, y: i32) -> Result<Vec<Cuadro>, String> {
+        let mut simulacion = self.clone();
+        simulacion.detonar(x, y)?;
+
+        let mut actual = self.clone();
+        actual.eventos.clear();
+        let mut cuadros = vec![Cuadro {
+            tablero: actual.clone(),
+            descripcion: "Estado inicial".to_string(),
+        }];
+
+        for evento in simulacion.eventos {
+            let descripcion = aplicar_evento(&mut actual, &evento);
+            // Igual que al detonar, el suelo de una celda que se queda sin ocupante pasa a la
+            // cuadrícula.
+            actual.liberar_suelo();
+            actual.eventos.push(evento);
+            cuadros.push(Cuadro {
+                tablero: actual.clone(),
+                descripcion,
+            });
+        }
+        Ok(cuadros)
+    }
+}
+
+fn aplicar_evento(tablero: &mut Tablero, evento: &Evento) -> String {
+    match *evento {
+        Evento::Detonacion(x, y) => {
+            tablero.cuadricula[y as usize][x as usize] = Objeto::Vacio;
+            format!("Explota la bomba en ({}, {})", x, y)
+        }
+        Evento::Alcance(x, y, (ox, oy)) => {
+            format!("El rayo de ({}, {}) alcanza ({}, {})", ox, oy, x, y)
+        }
+        Evento::Conmutacion(x, y, (ox, oy)) => {
+            tablero.conmutar_en(x as usize, y as usize);
+            format!(
+                "El interruptor de ({}, {}) cambia el objeto en ({}, {})",
+                ox, oy, x, y
+            )
+        }
+        Evento::Impacto(x, y, (ox, oy), vida) => {
+            let celda = &mut tablero.cuadricula[y as usize][x as usize];
+            if vida == 0 {
+                *celda = Objeto::Vacio;
+                format!(
+                    "El rayo de ({}, {}) elimina al enemigo en ({}, {})",
+                    ox, oy, x, y
+                )
+            } else {
+                let (mut afectadas, tipo) = match celda {
+                    Objeto::Enemigo(_, afectadas, tipo) => (afectadas.clone(), *tipo),
+                    _ => (HashSet::new(), TipoDeEnemigo::Comun),
+                };
+                afectadas.insert((ox, oy));
+                *celda = Objeto::Enemigo(vida, afectadas, tipo);
+                format!(
+                    "El rayo de ({}, {}) golpea al enemigo en ({}, {}), vida restante: {}",
+                    ox, oy, x, y, vida
+                )
+            }
+        }
+    }
+}
+
+/// Muestra los cuadros de una animación uno a uno.
+///
+/// # Argumentos
+///
+/// * `cuadros`: Los cuadros a mostrar, en orden.
+/// * `salida`: Donde se dibujan los cuadros.
+/// * `entrada`: De donde se leen las pulsaciones de Enter en el modo manual.
+/// * `ritmo`: Cómo avanzar entre cuadros.
+/// * `color`: Si es `true` limpia la pantalla entre cuadros y dibuja con colores ANSI.
+///
+pub fn reproducir<W: Write, R: BufRead>(
+    cuadros: &[Cuadro],
+    salida: &mut W,
+    entrada: &mut R,
+    ritmo: &Ritmo,
+    color: bool,
+) -> Result<(), io::Error> {
+    for (indice, cuadro) in cuadros.iter().enumerate() {
+        if color {
+            write!(salida, "{}", LIMPIAR_PANTALLA)?;
+        }
+        writeln!(
+            salida,
+            "[{}/{}] {}",
+            indice + 1,
+            cuadros.len(),
+            cuadro.descripcion
+        )?;
+        write!(salida, "{}", cuadro.tablero.renderizar(color))?;
+        salida.flush()?;
+
+        if indice + 1 == cuadros.len() {
+            break;
+        }
+        match ritmo {
+            Ritmo::Intervalo(espera) => thread::sleep(*espera),
+            Ritmo::Manual => {
+                let mut linea = String::new();
+                if entrada.read_line(&mut linea)? == 0 {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tablero::leer_tablero;
+    use crate::tablero::model::direccion::{Direccion, Direcciones};
+
+    #[test]
+    fn test_animar_detonacion() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Desvio(Direccion::Abajo, None),
+            ],
+            vec![
+                Objeto::Pared,
+                Objeto::Pared,
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![Objeto::Vacio, Objeto::Vacio, Objeto::Vacio],
+        ];
+        let cuadros = tablero.animar_detonacion(0, 0).unwrap_or_default();
+
+        let descripciones: Vec<&str> = cuadros.iter().map(|c| c.descripcion.as_str()).collect();
+        assert_eq!(
+            descripciones,
+            vec![
+                "Estado inicial",
+                "Explota la bomba en (0, 0)",
+                "El rayo de (0, 0) alcanza (1, 0)",
+                "Explota la bomba en (1, 0)",
+                "El rayo de (1, 0) alcanza (0, 0)",
+                "El rayo de (1, 0) alcanza (2, 0)",
+            ]
+        );
+        assert_eq!(cuadros[0].tablero.cuadricula, tablero.cuadricula);
+        assert_eq!(
+            cuadros[2].tablero.cuadricula[0][1],
+            Objeto::Bomba(false, 1, Direcciones::ORTOGONALES)
+        );
+        assert_eq!(cuadros[3].tablero.cuadricula[0][1], Objeto::Vacio);
+        assert_eq!(cuadros[5].tablero.eventos.len(), 5);
+    }
+
+    #[test]
+    fn test_animar_bomba_sobre_suelo() {
+        let tablero = leer_tablero("B1 B1+DD _\n_ _ _\n_ F1 _\n".as_bytes()).unwrap();
+        let cuadros = tablero.animar_detonacion(0, 0).unwrap_or_default();
+        let mut detonado = tablero.clone();
+        assert!(detonado.detonar(0, 0).is_ok());
+
+        let ultimo = cuadros.last().map(|c| c.tablero.clone());
+        assert!(ultimo.is_some_and(|ultimo| {
+            ultimo.cuadricula == detonado.cuadricula && ultimo.suelo == detonado.suelo
+        }));
+        assert_eq!(
+            detonado.cuadricula[0][1],
+            Objeto::Desvio(Direccion::Abajo, None)
+        );
+    }
+
+    #[test]
+    fn test_animar_golpes_a_enemigo() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                Objeto::Desvio(Direccion::Abajo, None),
+            ],
+            vec![
+                Objeto::Pared,
+                Objeto::Pared,
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![Objeto::Vacio, Objeto::Vacio, Objeto::Vacio],
+        ];
+        let cuadros = tablero.animar_detonacion(1, 0).unwrap_or_default();
+
+        let ultimo = &cuadros[cuadros.len() - 1];
+        assert_eq!(
+            ultimo.descripcion,
+            "El rayo de (1, 0) golpea al enemigo en (2, 1), vida restante: 1"
+        );
+        let mut afectadas = HashSet::new();
+        afectadas.insert((1, 0));
+        assert_eq!(
+            ultimo.tablero.cuadricula[1][2],
+            Objeto::Enemigo(1, afectadas, TipoDeEnemigo::Comun)
+        );
+        // La animación no modifica el tablero original.
+        assert_eq!(
+            tablero.cuadricula[0][1],
+            Objeto::Bomba(false, 2, Direcciones::ORTOGONALES)
+        );
+    }
+
+    #[test]
+    fn test_animar_interruptor() {
+        let tablero = leer_tablero("B2 C1 F1\nI1 _ _\n_ _ _\n".as_bytes()).unwrap();
+        let cuadros = tablero.animar_detonacion(0, 0).unwrap_or_default();
+
+        assert!(cuadros
+            .iter()
+            .any(|c| c.descripcion == "El interruptor de (0, 1) cambia el objeto en (1, 0)"));
+        let mut detonado = tablero.clone();
+        assert!(detonado.detonar(0, 0).is_ok());
+        assert_eq!(cuadros[cuadros.len() - 1].tablero, detonado);
+    }
+
+    #[test]
+    fn test_animar_sin_bomba() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Desvio(Direccion::Abajo, None),
+            ],
+            vec![
+                Objeto::Pared,
+                Objeto::Pared,
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![Objeto::Vacio, Objeto::Vacio, Objeto::Vacio],
+        ];
+        assert!(tablero.animar_detonacion(2, 2).is_err());
+    }
+
+    #[test]
+    fn test_reproducir_manual() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Desvio(Direccion::Abajo, None),
+            ],
+            vec![
+                Objeto::Pared,
+                Objeto::Pared,
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![Objeto::Vacio, Objeto::Vacio, Objeto::Vacio],
+        ];
+        let cuadros = tablero.animar_detonacion(0, 0).unwrap_or_default();
+
+        let mut salida = Vec::new();
+        let mut entrada = io::Cursor::new("\n\n");
+        let resultado = reproducir(&cuadros, &mut salida, &mut entrada, &Ritmo::Manual, false);
+        assert!(resultado.is_ok());
+
+        // Solo hay dos pulsaciones de Enter, así que se muestran tres cuadros.
+        let texto = String::from_utf8_lossy(&salida);
+        assert!(texto.contains("[1/6] Estado inicial"));
+        assert!(texto.contains("[3/6] El rayo de (0, 0) alcanza (1, 0)"));
+        assert!(!texto.contains("[4/6]"));
+    }
+
+    #[test]
+    fn test_reproducir_con_intervalo() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Desvio(Direccion::Abajo, None),
+            ],
+            vec![
+                Objeto::Pared,
+                Objeto::Pared,
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![Objeto::Vacio, Objeto::Vacio, Objeto::Vacio],
+        ];
+        let cuadros = tablero.animar_detonacion(0, 0).unwrap_or_default();
+
+        let mut salida = Vec::new();
+        let mut entrada = io::Cursor::new("");
+        let ritmo = Ritmo::Intervalo(Duration::from_millis(0));
+        assert!(reproducir(&cuadros, &mut salida, &mut entrada, &ritmo, false).is_ok());
+        assert!(String::from_utf8_lossy(&salida).contains("[6/6]"));
+    }
+}
@@ -0,0 +1,193 @@
+use super::renderizado::FONDO_CAMBIO;
+use super::Tablero;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Representa una celda que cambió entre dos estados de un tablero. Cada estado se guarda como
+/// el símbolo del objeto, o `None` si la celda no existe en ese tablero.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Cambio {
+    pub posicion: (i32, i32),
+    pub antes: Option<String>,
+    pub despues: Option<String>,
+}
+
+impl fmt::Display for Cambio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (x, y) = self.posicion;
+        write!(
+            f,
+            "({},{}): {} -> {}",
+            x,
+            y,
+            self.antes.as_deref().unwrap_or("-"),
+            self.despues.as_deref().unwrap_or("-")
+        )
+    }
+}
+
+impl Tablero {
+    /// Compara el tablero con `otro` celda por celda. Dos celdas son iguales si tienen el mismo
+    /// símbolo, así que no se distinguen enemigos con la misma vida golpeados por otras bombas.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve los cambios ordenados por fila y columna. Si los tableros tienen distinto
+    /// tamaño, las celdas que existen en uno solo también se informan como cambios.
+    pub fn diferencias(&self, otro: &Tablero) -> Vec<Cambio> {
+        let filas = self.cuadricula.len().max(otro.cuadricula.len());
+        let mut cambios = Vec::new();
+        for y in 0..filas {
+            let columnas = ancho_de_fila(self, y).max(ancho_de_fila(otro, y));
+            for x in 0..columnas {
+                let antes = self.simbolo_en(x, y);
+                let despues = otro.simbolo_en(x, y);
+                if antes != despues {
+                    cambios.push(Cambio {
+                        posicion: (x as i32, y as i32),
+                        antes,
+                        despues,
+                    });
+                }
+            }
+        }
+        cambios
+    }
+
+    /// Dibuja `otro` resaltando las celdas que cambiaron respecto del tablero.
+    ///
+    /// # Argumentos
+    ///
+    /// * `otro`: El tablero a dibujar, por ejemplo el resultado de una detonación.
+    /// * `color`: Si es `true` resalta los cambios con fondo rojo; si es `false` los marca
+    ///   entre corchetes.
+    ///
+    pub fn renderizar_diferencias(&self, otro: &Tablero, color: bool) -> String {
+        let cambiadas: HashSet<(i32, i32)> = self
+            .diferencias(otro)
+            .into_iter()
+            .map(|cambio| cambio.posicion)
+            .collect();
+        otro.dibujar(&cambiadas, FONDO_CAMBIO, color)
+    }
+}
+
+/// Devuelve los cambios como texto, uno por línea con el formato `(x,y): antes -> después`.
+pub fn diferencias_a_texto(cambios: &[Cambio]) -> String {
+    cambios
+        .iter()
+        .map(|cambio| format!("{}\n", cambio))
+        .collect()
+}
+
+/// Devuelve los cambios en formato CSV con las columnas `x,y,antes,despues`. Las celdas que no
+/// existen en uno de los tableros quedan vacías.
+pub fn diferencias_a_csv(cambios: &[Cambio]) -> String {
+    let mut csv = String::from("x,y,antes,despues\n");
+    for cambio in cambios {
+        csv.push_str(&format!(
+            "{},{},{},{}\n",
+            cambio.posicion.0,
+            cambio.posicion.1,
+            cambio.antes.as_deref().unwrap_or(""),
+            cambio.despues.as_deref().unwrap_or("")
+        ));
+    }
+    csv
+}
+
+fn ancho_de_fila(tablero: &Tablero, y: usize) -> usize {
+    tablero.cuadricula.get(y).map_or(0, |fila| fila.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tablero::model::direccion::Direcciones;
+    use crate::tablero::model::enemigo::TipoDeEnemigo;
+    use crate::tablero::model::objeto::Objeto;
+
+    #[test]
+    fn test_diferencias_luego_de_detonar() {
+        let mut antes = Tablero::new(2);
+        antes.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![Objeto::Pared, Objeto::Vacio],
+        ];
+        let mut despues = antes.clone();
+        assert!(despues.detonar(0, 0).is_ok());
+
+        let cambios = antes.diferencias(&despues);
+        assert_eq!(
+            diferencias_a_texto(&cambios),
+            "(0,0): B1 -> _\n(1,0): F2 -> F1\n"
+        );
+        assert_eq!(
+            diferencias_a_csv(&cambios),
+            "x,y,antes,despues\n0,0,B1,_\n1,0,F2,F1\n"
+        );
+    }
+
+    #[test]
+    fn test_diferencias_entre_tableros_iguales() {
+        let mut tablero = Tablero::new(2);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![Objeto::Pared, Objeto::Vacio],
+        ];
+        assert!(tablero.diferencias(&tablero.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_diferencias_con_distinto_tamaño() {
+        let mut antes = Tablero::new(2);
+        antes.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![Objeto::Pared, Objeto::Vacio],
+        ];
+        let mut despues = antes.clone();
+        despues.cuadricula.pop();
+        despues.cuadricula[0].push(Objeto::Roca);
+
+        let cambios = antes.diferencias(&despues);
+        assert_eq!(
+            diferencias_a_texto(&cambios),
+            "(2,0): - -> R\n(0,1): W -> -\n(1,1): _ -> -\n"
+        );
+        assert_eq!(
+            diferencias_a_csv(&cambios),
+            "x,y,antes,despues\n2,0,,R\n0,1,W,\n1,1,_,\n"
+        );
+    }
+
+    #[test]
+    fn test_renderizar_diferencias() {
+        let mut antes = Tablero::new(2);
+        antes.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![Objeto::Pared, Objeto::Vacio],
+        ];
+        let mut despues = antes.clone();
+        assert!(despues.detonar(0, 0).is_ok());
+
+        assert_eq!(
+            antes.renderizar_diferencias(&despues, false),
+            "[_ ][F1]\n W   _  \n"
+        );
+        assert!(antes
+            .renderizar_diferencias(&despues, true)
+            .starts_with(FONDO_CAMBIO));
+    }
+}
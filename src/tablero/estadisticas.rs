@@ -0,0 +1,365 @@
+use super::model::objeto::Objeto;
+use super::resolucion::Resolucion;
+use super::Tablero;
+use std::collections::BTreeMap;
+
+/// Representa las estadísticas de un tablero: cantidad de cada tipo de objeto, distribución de
+/// alcances de las bombas, desvíos por dirección y, si se pidieron, datos de resolución.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Estadisticas {
+    pub filas: usize,
+    pub columnas: usize,
+    pub vacios: usize,
+    pub rocas: usize,
+    pub paredes: usize,
+    pub enemigos: usize,
+    pub vida_total: i32,
+    /// Cantidad de enemigos por nombre de tipo.
+    pub tipos_de_enemigo: BTreeMap<&'static str, usize>,
+    pub bombas_normales: usize,
+    pub bombas_traspaso: usize,
+    pub bombas_de_area: usize,
+    /// Cantidad de bombas por cada valor de alcance.
+    pub alcances: BTreeMap<i32, usize>,
+    /// Cantidad de desvíos por nombre de dirección.
+    pub desvios: BTreeMap<&'static str, usize>,
+    pub espejos: usize,
+    pub divisores: usize,
+    pub portales: usize,
+    pub interruptores: usize,
+    pub compuertas: usize,
+    /// Resultado de la búsqueda de resolución, o `None` si no se calculó.
+    pub resolucion: Option<Resolucion>,
+    /// Cantidad de bombas que explotan en la cadena más larga.
+    pub cadena_mas_larga: usize,
+}
+
+/// Representa los cambios entre las estadísticas de un tablero antes y después de detonar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diferencia {
+    pub enemigos_eliminados: usize,
+    pub vida_quitada: i32,
+    pub bombas_consumidas: usize,
+}
+
+impl Estadisticas {
+    /// Devuelve la cantidad total de bombas, normales, de traspaso y de área.
+    pub fn bombas(&self) -> usize {
+        self.bombas_normales + self.bombas_traspaso + self.bombas_de_area
+    }
+
+    /// Calcula los cambios entre estas estadísticas y las del mismo tablero luego de detonar.
+    pub fn diferencia(&self, despues: &Estadisticas) -> Diferencia {
+        Diferencia {
+            enemigos_eliminados: self.enemigos.saturating_sub(despues.enemigos),
+            vida_quitada: self.vida_total - despues.vida_total,
+            bombas_consumidas: self.bombas().saturating_sub(despues.bombas()),
+        }
+    }
+
+    /// Devuelve las estadísticas como texto legible, una por línea.
+    pub fn a_texto(&self) -> String {
+        let alcances: Vec<String> = self
+            .alcances
+            .iter()
+            .map(|(alcance, cantidad)| format!("{}: {}", alcance, cantidad))
+            .collect();
+        let desvios: Vec<String> = self
+            .desvios
+            .iter()
+            .map(|(direccion, cantidad)| format!("{}: {}", direccion, cantidad))
+            .collect();
+        let tipos: Vec<String> = self
+            .tipos_de_enemigo
+            .iter()
+            .map(|(tipo, cantidad)| format!("{}: {}", tipo, cantidad))
+            .collect();
+
+        let mut texto = String::new();
+        texto.push_str(&format!("Tamaño: {}x{}\n", self.columnas, self.filas));
+        texto.push_str(&format!(
+            "Vacíos: {}\nRocas: {}\nParedes: {}\n",
+            self.vacios, self.rocas, self.paredes
+        ));
+        texto.push_str(&format!(
+            "Enemigos: {} (vida total: {})\n",
+            self.enemigos, self.vida_total
+        ));
+        texto.push_str(&format!("Tipos de enemigo: {}\n", tipos.join(", ")));
+        texto.push_str(&format!(
+            "Bombas: {} (normales: {}, traspaso: {}, área: {})\n",
+            self.bombas(),
+            self.bombas_normales,
+            self.bombas_traspaso,
+            self.bombas_de_area
+        ));
+        texto.push_str(&format!("Alcances: {}\n", alcances.join(", ")));
+        texto.push_str(&format!("Desvíos: {}\n", desvios.join(", ")));
+        texto.push_str(&format!("Espejos: {}\n", self.espejos));
+        texto.push_str(&format!("Divisores: {}\n", self.divisores));
+        texto.push_str(&format!("Portales: {}\n", self.portales));
+        texto.push_str(&format!(
+            "Interruptores: {} (compuertas: {})\n",
+            self.interruptores, self.compuertas
+        ));
+        if let Some(resolucion) = self.resolucion {
+            let (resoluble, detonaciones) = match resolucion {
+                Resolucion::Resoluble(cantidad) => ("sí", cantidad.to_string()),
+                Resolucion::Irresoluble => ("no", "-".to_string()),
+                Resolucion::Desconocida => ("desconocido", "-".to_string()),
+            };
+            texto.push_str(&format!("Resoluble: {}\n", resoluble));
+            texto.push_str(&format!("Detonaciones mínimas: {}\n", detonaciones));
+        }
+        texto.push_str(&format!(
+            "Cadena más larga: {} bombas\n",
+            self.cadena_mas_larga
+        ));
+        texto
+    }
+
+    /// Devuelve las estadísticas como un objeto JSON en una sola línea.
+    pub fn a_json(&self) -> String {
+        let alcances: Vec<String> = self
+            .alcances
+            .iter()
+            .map(|(alcance, cantidad)| format!("\"{}\":{}", alcance, cantidad))
+            .collect();
+        let desvios: Vec<String> = self
+            .desvios
+            .iter()
+            .map(|(direccion, cantidad)| format!("\"{}\":{}", direccion, cantidad))
+            .collect();
+        let tipos: Vec<String> = self
+            .tipos_de_enemigo
+            .iter()
+            .map(|(tipo, cantidad)| format!("\"{}\":{}", tipo, cantidad))
+            .collect();
+        let (resoluble, detonaciones) = match self.resolucion {
+            Some(Resolucion::Resoluble(cantidad)) => ("true", cantidad.to_string()),
+            Some(Resolucion::Irresoluble) => ("false", "null".to_string()),
+            Some(Resolucion::Desconocida) | None => ("null", "null".to_string()),
+        };
+        format!(
+            "{{\"filas\":{},\"columnas\":{},\"vacios\":{},\"rocas\":{},\"paredes\":{},\"enemigos\":{},\"vida_total\":{},\"tipos_de_enemigo\":{{{}}},\"bombas\":{},\"bombas_normales\":{},\"bombas_traspaso\":{},\"bombas_de_area\":{},\"alcances\":{{{}}},\"desvios\":{{{}}},\"espejos\":{},\"divisores\":{},\"portales\":{},\"interruptores\":{},\"compuertas\":{},\"resoluble\":{},\"detonaciones_minimas\":{},\"cadena_mas_larga\":{}}}",
+            self.filas,
+            self.columnas,
+            self.vacios,
+            self.rocas,
+            self.paredes,
+            self.enemigos,
+            self.vida_total,
+            tipos.join(","),
+            self.bombas(),
+            self.bombas_normales,
+            self.bombas_traspaso,
+            self.bombas_de_area,
+            alcances.join(","),
+            desvios.join(","),
+            self.espejos,
+            self.divisores,
+            self.portales,
+            self.interruptores,
+            self.compuertas,
+            resoluble,
+            detonaciones,
+            self.cadena_mas_larga
+        )
+    }
+}
+
+impl Diferencia {
+    /// Devuelve los cambios como texto legible, uno por línea.
+    pub fn a_texto(&self) -> String {
+        format!(
+            "Enemigos eliminados: {}\nVida quitada: {}\nBombas consumidas: {}\n",
+            self.enemigos_eliminados, self.vida_quitada, self.bombas_consumidas
+        )
+    }
+
+    /// Devuelve los cambios como un objeto JSON en una sola línea.
+    pub fn a_json(&self) -> String {
+        format!(
+            "{{\"enemigos_eliminados\":{},\"vida_quitada\":{},\"bombas_consumidas\":{}}}",
+            self.enemigos_eliminados, self.vida_quitada, self.bombas_consumidas
+        )
+    }
+}
+
+impl Tablero {
+    /// Calcula las estadísticas del tablero en su estado actual, sin los datos de resolución,
+    /// que pueden tardar mucho en tableros con muchas bombas.
+    pub fn estadisticas(&self) -> Estadisticas {
+        let mut estadisticas = Estadisticas {
+            filas: self.cuadricula.len(),
+            columnas: self.tamaño as usize,
+            ..Estadisticas::default()
+        };
+
+        // Las celdas con suelo se cuentan por el ocupante y por el suelo, y no cuentan como
+        // vacías aunque ya no tengan ocupante.
+        for ((x, y), objeto) in self.objetos_con_suelo() {
+            match objeto {
+                Objeto::Vacio if *self.suelo_en(x, y) != Objeto::Vacio => {}
+                Objeto::Vacio => estadisticas.vacios += 1,
+                Objeto::Roca => estadisticas.rocas += 1,
+                Objeto::Pared => estadisticas.paredes += 1,
+                Objeto::Enemigo(vida, _, tipo) => {
+                    estadisticas.enemigos += 1;
+                    *estadisticas
+                        .tipos_de_enemigo
+                        .entry(tipo.nombre())
+                        .or_insert(0) += 1;
+                    estadisticas.vida_total += vida;
+                }
+                Objeto::Bomba(traspaso, alcance, _) => {
+                    if *traspaso {
+                        estadisticas.bombas_traspaso += 1;
+                    } else {
+                        estadisticas.bombas_normales += 1;
+                    }
+                    *estadisticas.alcances.entry(*alcance).or_insert(0) += 1;
+                }
+                Objeto::BombaDeArea(_, alcance, _) => {
+                    estadisticas.bombas_de_area += 1;
+                    *estadisticas.alcances.entry(*alcance).or_insert(0) += 1;
+                }
+                Objeto::Desvio(direccion, _) => {
+                    *estadisticas.desvios.entry(direccion.nombre()).or_insert(0) += 1;
+                }
+                Objeto::Espejo(_) => estadisticas.espejos += 1,
+                Objeto::Divisor => estadisticas.divisores += 1,
+                Objeto::Portal(_) => estadisticas.portales += 1,
+                Objeto::Interruptor(_) => estadisticas.interruptores += 1,
+                Objeto::Compuerta(_, _) => estadisticas.compuertas += 1,
+            }
+        }
+
+        estadisticas.cadena_mas_larga = self.grafo_de_bombas().cadenas_mas_largas().1;
+        estadisticas
+    }
+
+    /// Calcula las estadísticas del tablero incluyendo la búsqueda de resolución.
+    pub fn estadisticas_con_resolucion(&self) -> Estadisticas {
+        Estadisticas {
+            resolucion: Some(self.resolucion()),
+            ..self.estadisticas()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tablero::model::direccion::{Direccion, Direcciones};
+    use crate::tablero::model::enemigo::TipoDeEnemigo;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_estadisticas() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+                Objeto::Desvio(Direccion::Abajo, None),
+            ],
+            vec![
+                Objeto::Pared,
+                Objeto::Roca,
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![
+                Objeto::Bomba(true, 2, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+                Objeto::Desvio(Direccion::Arriba, None),
+            ],
+        ];
+        let estadisticas = tablero.estadisticas();
+
+        assert_eq!((estadisticas.filas, estadisticas.columnas), (3, 3));
+        assert_eq!(estadisticas.vacios, 1);
+        assert_eq!(estadisticas.rocas, 1);
+        assert_eq!(estadisticas.paredes, 1);
+        assert_eq!(estadisticas.enemigos, 2);
+        assert_eq!(estadisticas.vida_total, 3);
+        assert_eq!(estadisticas.bombas(), 2);
+        assert_eq!(estadisticas.bombas_traspaso, 1);
+        assert_eq!(estadisticas.alcances, [(2, 2)].into_iter().collect());
+        assert_eq!(
+            estadisticas.desvios,
+            [("abajo", 1), ("arriba", 1)].into_iter().collect()
+        );
+        assert_eq!(estadisticas.resolucion, None);
+        assert_eq!(estadisticas.cadena_mas_larga, 1);
+        assert_eq!(
+            tablero.estadisticas_con_resolucion().resolucion,
+            Some(Resolucion::Irresoluble)
+        );
+    }
+
+    #[test]
+    fn test_diferencia_luego_de_detonar() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 3, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+                Objeto::Desvio(Direccion::Abajo, None),
+            ],
+            vec![
+                Objeto::Pared,
+                Objeto::Roca,
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![
+                Objeto::Bomba(true, 2, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+                Objeto::Desvio(Direccion::Arriba, None),
+            ],
+        ];
+        let antes = tablero.estadisticas();
+        assert!(tablero.detonar(0, 0).is_ok());
+        let despues = tablero.estadisticas();
+
+        assert_eq!(
+            antes.diferencia(&despues),
+            Diferencia {
+                enemigos_eliminados: 1,
+                vida_quitada: 2,
+                bombas_consumidas: 1,
+            }
+        );
+        assert_eq!(
+            antes.diferencia(&despues).a_json(),
+            "{\"enemigos_eliminados\":1,\"vida_quitada\":2,\"bombas_consumidas\":1}"
+        );
+    }
+
+    #[test]
+    fn test_a_texto_y_a_json() {
+        let mut tablero = Tablero::new(2);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![Objeto::Pared, Objeto::Vacio],
+        ];
+        assert!(!tablero.estadisticas().a_texto().contains("Resoluble"));
+        let estadisticas = tablero.estadisticas_con_resolucion();
+
+        let texto = estadisticas.a_texto();
+        assert!(texto.contains("Bombas: 1 (normales: 1, traspaso: 0, área: 0)\n"));
+        assert!(texto.contains("Enemigos: 1 (vida total: 1)\n"));
+        assert!(texto.contains("Tipos de enemigo: común: 1\n"));
+        assert!(texto.contains("Alcances: 1: 1\n"));
+        assert!(texto.contains("Resoluble: sí\n"));
+        assert!(texto.contains("Detonaciones mínimas: 1\n"));
+
+        assert_eq!(
+            estadisticas.a_json(),
+            "{\"filas\":2,\"columnas\":2,\"vacios\":1,\"rocas\":0,\"paredes\":1,\"enemigos\":1,\"vida_total\":1,\"tipos_de_enemigo\":{\"común\":1},\"bombas\":1,\"bombas_normales\":1,\"bombas_traspaso\":0,\"bombas_de_area\":0,\"alcances\":{\"1\":1},\"desvios\":{},\"espejos\":0,\"divisores\":0,\"portales\":0,\"interruptores\":0,\"compuertas\":0,\"resoluble\":true,\"detonaciones_minimas\":1,\"cadena_mas_larga\":1}"
+        );
+    }
+}
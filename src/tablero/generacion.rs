@@ -0,0 +1,264 @@
+use super::model::objeto::{convertir_simbolos, Objeto};
+use super::resolucion::Resolucion;
+use super::Tablero;
+
+/// Parámetros para generar tableros al azar.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Generacion {
+    /// Cantidad de filas y de columnas del tablero.
+    pub tamaño: i32,
+    /// Cantidad de bombas comunes del tablero.
+    pub bombas: usize,
+    /// Cantidad de enemigos; cada uno recibe 1 o 2 vidas.
+    pub enemigos: usize,
+    /// Cantidad de rocas.
+    pub rocas: usize,
+    /// Cantidad de paredes.
+    pub paredes: usize,
+    /// Cantidad de desvíos; cada uno recibe una dirección al azar.
+    pub desvios: usize,
+    /// Alcance máximo de las bombas; cada bomba recibe un alcance entre 1 y este valor.
+    pub alcance_maximo: i32,
+    /// Cantidad exacta de detonaciones manuales con la que se debe poder resolver el tablero,
+    /// o `None` si alcanza con que sea resoluble.
+    pub detonaciones: Option<usize>,
+    /// Cantidad de tableros que se prueban antes de darse por vencido.
+    pub intentos: usize,
+    /// Semilla del generador de números al azar: la misma semilla genera el mismo tablero.
+    pub semilla: u64,
+}
+
+impl Default for Generacion {
+    fn default() -> Self {
+        Generacion {
+            tamaño: 7,
+            bombas: 3,
+            enemigos: 3,
+            rocas: 2,
+            paredes: 2,
+            desvios: 1,
+            alcance_maximo: 3,
+            detonaciones: None,
+            intentos: 1000,
+            semilla: 1,
+        }
+    }
+}
+
+/// Generador de números al azar xorshift: alcanza para repartir objetos y no agrega
+/// dependencias.
+struct Azar(u64);
+
+impl Azar {
+    fn new(semilla: u64) -> Azar {
+        // xorshift se queda en cero para siempre si empieza en cero.
+        Azar(semilla.max(1))
+    }
+
+    fn siguiente(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Devuelve un número entre `0` y `limite - 1`.
+    fn hasta(&mut self, limite: usize) -> usize {
+        (self.siguiente() % limite as u64) as usize
+    }
+}
+
+/// Genera un tablero al azar que se puede resolver, probando tableros hasta encontrar uno que
+/// cumpla con los parámetros.
+///
+/// # Argumentos
+///
+/// * `parametros`: La cantidad de cada tipo de objeto, el tamaño, la cantidad de detonaciones
+///   pedida, los intentos y la semilla.
+///
+/// # Devuelve
+///
+/// Devuelve el tablero generado, o un mensaje de error si los parámetros no son válidos o si
+/// ninguno de los intentos dio un tablero resoluble con la cantidad de detonaciones pedida.
+/// Los tableros para los que la búsqueda de resolución no llega a una respuesta se descartan.
+pub fn generar(parametros: &Generacion) -> Result<Tablero, String> {
+    validar_parametros(parametros)?;
+    let mut azar = Azar::new(parametros.semilla);
+    for _ in 0..parametros.intentos {
+        let tablero = tablero_al_azar(parametros, &mut azar)?;
+        if let Resolucion::Resoluble(cantidad) = tablero.resolucion() {
+            if parametros
+                .detonaciones
+                .is_none_or(|pedidas| pedidas == cantidad)
+            {
+                return Ok(tablero);
+            }
+        }
+    }
+    Err(format!(
+        "No se encontró un tablero resoluble en {} intentos",
+        parametros.intentos
+    ))
+}
+
+fn validar_parametros(parametros: &Generacion) -> Result<(), String> {
+    if parametros.tamaño < 1 {
+        return Err("El tamaño del tablero debe ser al menos 1".to_string());
+    }
+    if parametros.alcance_maximo < 1 {
+        return Err("El alcance máximo de las bombas debe ser al menos 1".to_string());
+    }
+    let celdas = (parametros.tamaño as usize).pow(2);
+    let objetos = parametros.bombas
+        + parametros.enemigos
+        + parametros.rocas
+        + parametros.paredes
+        + parametros.desvios;
+    if objetos > celdas {
+        return Err(format!(
+            "No entran {} objetos en un tablero de {} celdas",
+            objetos, celdas
+        ));
+    }
+    if parametros.enemigos > 0 && parametros.bombas == 0 {
+        return Err("Hace falta al menos una bomba para eliminar a los enemigos".to_string());
+    }
+    if let Some(detonaciones) = parametros.detonaciones {
+        // Un enemigo con dos vidas puede necesitar dos detonaciones, así que el máximo lo
+        // pone la cantidad de bombas; si el tablero existe lo decide la resolución.
+        let minimo = usize::from(parametros.enemigos > 0);
+        let maximo = if parametros.enemigos > 0 {
+            parametros.bombas
+        } else {
+            0
+        };
+        if detonaciones < minimo || detonaciones > maximo {
+            return Err(format!(
+                "La cantidad de detonaciones debe estar entre {} y {}",
+                minimo, maximo
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Reparte los objetos en celdas elegidas al azar, sin repetir celdas.
+fn tablero_al_azar(parametros: &Generacion, azar: &mut Azar) -> Result<Tablero, String> {
+    let tamaño = parametros.tamaño as usize;
+    let mut celdas: Vec<(usize, usize)> = (0..tamaño)
+        .flat_map(|y| (0..tamaño).map(move |x| (x, y)))
+        .collect();
+    for i in (1..celdas.len()).rev() {
+        celdas.swap(i, azar.hasta(i + 1));
+    }
+
+    let mut simbolos = Vec::new();
+    for _ in 0..parametros.bombas {
+        let alcance = 1 + azar.hasta(parametros.alcance_maximo as usize);
+        simbolos.push(format!("B{}", alcance));
+    }
+    for _ in 0..parametros.enemigos {
+        simbolos.push(format!("F{}", 1 + azar.hasta(2)));
+    }
+    simbolos.extend((0..parametros.rocas).map(|_| "R".to_string()));
+    simbolos.extend((0..parametros.paredes).map(|_| "W".to_string()));
+    for _ in 0..parametros.desvios {
+        simbolos.push(format!("D{}", ["U", "D", "L", "R"][azar.hasta(4)]));
+    }
+
+    let mut tablero = Tablero::new(parametros.tamaño);
+    tablero.cuadricula = vec![vec![Objeto::Vacio; tamaño]; tamaño];
+    for (simbolo, (x, y)) in simbolos.iter().zip(celdas) {
+        tablero.cuadricula[y][x] = convertir_simbolos(simbolo)?;
+    }
+    Ok(tablero)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generar_tablero_resoluble() {
+        let parametros = Generacion {
+            semilla: 42,
+            ..Generacion::default()
+        };
+        let tablero = generar(&parametros);
+        assert!(tablero.as_ref().is_ok_and(|tablero| tablero.es_resoluble()));
+        let tablero = tablero.unwrap_or_else(|_| Tablero::new(0));
+        assert_eq!(tablero.posiciones_bombas().len(), 3);
+        assert_eq!(tablero.cantidad_enemigos(), 3);
+
+        let otra_vez = generar(&parametros).unwrap_or_else(|_| Tablero::new(0));
+        assert_eq!(tablero.cuadricula, otra_vez.cuadricula);
+    }
+
+    #[test]
+    fn test_generar_con_detonaciones_exactas() {
+        for detonaciones in 1..=2 {
+            let parametros = Generacion {
+                tamaño: 6,
+                bombas: 4,
+                enemigos: 4,
+                detonaciones: Some(detonaciones),
+                semilla: 7,
+                ..Generacion::default()
+            };
+            let tablero = generar(&parametros);
+            assert!(tablero.is_ok_and(|tablero| {
+                tablero.resolucion() == Resolucion::Resoluble(detonaciones)
+            }));
+        }
+    }
+
+    #[test]
+    fn test_generar_enemigo_que_necesita_mas_detonaciones() {
+        let parametros = Generacion {
+            tamaño: 3,
+            bombas: 2,
+            enemigos: 1,
+            rocas: 0,
+            paredes: 0,
+            desvios: 0,
+            alcance_maximo: 1,
+            detonaciones: Some(2),
+            ..Generacion::default()
+        };
+        let tablero = generar(&parametros);
+        assert!(tablero.is_ok_and(|tablero| tablero.resolucion() == Resolucion::Resoluble(2)));
+    }
+
+    #[test]
+    fn test_generar_con_parametros_invalidos() {
+        let sin_lugar = Generacion {
+            tamaño: 2,
+            ..Generacion::default()
+        };
+        assert!(generar(&sin_lugar).is_err());
+
+        let sin_bombas = Generacion {
+            bombas: 0,
+            ..Generacion::default()
+        };
+        assert!(generar(&sin_bombas).is_err());
+
+        let demasiadas_detonaciones = Generacion {
+            detonaciones: Some(4),
+            ..Generacion::default()
+        };
+        assert!(generar(&demasiadas_detonaciones).is_err());
+    }
+
+    #[test]
+    fn test_generar_sin_intentos() {
+        let parametros = Generacion {
+            intentos: 0,
+            ..Generacion::default()
+        };
+        assert_eq!(
+            generar(&parametros).map(|tablero| tablero.tamaño),
+            Err("No se encontró un tablero resoluble en 0 intentos".to_string())
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use super::model::objeto::convertir_a_simbolo;
+use super::Tablero;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Representa el grafo de dependencias entre bombas: existe una arista de `A` a `B` si la
+/// explosión de `A` alcanza a `B` y la hace detonar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GrafoDeBombas {
+    /// Bombas del tablero con el símbolo que las representa.
+    pub nodos: BTreeMap<(i32, i32), String>,
+    /// Bombas que activa directamente cada bomba.
+    pub aristas: BTreeMap<(i32, i32), BTreeSet<(i32, i32)>>,
+}
+
+impl GrafoDeBombas {
+    /// Devuelve todas las bombas que terminan explotando si se detona `bomba`, incluida ella misma.
+    pub fn cadena(&self, bomba: (i32, i32)) -> BTreeSet<(i32, i32)> {
+        let mut visitadas = BTreeSet::new();
+        let mut pendientes = vec![bomba];
+        while let Some(actual) = pendientes.pop() {
+            if !self.nodos.contains_key(&actual) || !visitadas.insert(actual) {
+                continue;
+            }
+            if let Some(siguientes) = self.aristas.get(&actual) {
+                pendientes.extend(siguientes.iter().copied());
+            }
+        }
+        visitadas
+    }
+
+    /// Devuelve las bombas que inician las cadenas más largas, junto con la cantidad de bombas
+    /// que explotan en esas cadenas.
+    pub fn cadenas_mas_largas(&self) -> (Vec<(i32, i32)>, usize) {
+        let mut mejores = Vec::new();
+        let mut longitud_maxima = 0;
+        for &bomba in self.nodos.keys() {
+            let longitud = self.cadena(bomba).len();
+            if longitud > longitud_maxima {
+                longitud_maxima = longitud;
+                mejores.clear();
+            }
+            if longitud == longitud_maxima {
+                mejores.push(bomba);
+            }
+        }
+        (mejores, longitud_maxima)
+    }
+
+    /// Exporta el grafo en formato DOT de Graphviz. Cada nodo se etiqueta con el símbolo de la
+    /// bomba, su posición y el largo de la cadena que inicia.
+    pub fn a_dot(&self) -> String {
+        let mut dot = String::from("digraph bombas {\n");
+        for (&(x, y), simbolo) in &self.nodos {
+            dot.push_str(&format!(
+                "    \"{},{}\" [label=\"{} ({}, {})\\ncadena: {}\"];\n",
+                x,
+                y,
+                simbolo,
+                x,
+                y,
+                self.cadena((x, y)).len()
+            ));
+        }
+        for (&(x, y), destinos) in &self.aristas {
+            for &(dx, dy) in destinos {
+                dot.push_str(&format!("    \"{},{}\" -> \"{},{}\";\n", x, y, dx, dy));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl Tablero {
+    /// Construye el grafo de dependencias entre las bombas del tablero, usando el alcance de
+    /// cada explosión (con desvíos y bloqueos incluidos) para decidir qué bombas activa.
+    pub fn grafo_de_bombas(&self) -> GrafoDeBombas {
+        let mut nodos = BTreeMap::new();
+        let mut aristas = BTreeMap::new();
+
+        for (x, y) in self.posiciones_bombas() {
+            nodos.insert(
+                (x, y),
+                convertir_a_simbolo(&self.cuadricula[y as usize][x as usize]),
+            );
+        }
+
+        for &bomba in nodos.keys() {
+            let activadas: BTreeSet<(i32, i32)> = self
+                .celdas_alcanzadas(bomba)
+                .into_iter()
+                .filter(|celda| *celda != bomba && nodos.contains_key(celda))
+                .collect();
+            aristas.insert(bomba, activadas);
+        }
+
+        GrafoDeBombas { nodos, aristas }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tablero::model::direccion::{Direccion, Direcciones};
+    use crate::tablero::model::objeto::Objeto;
+
+    #[test]
+    fn test_grafo_de_bombas() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Bomba(false, 3, Direcciones::ORTOGONALES),
+                Objeto::Desvio(Direccion::Abajo, None),
+            ],
+            vec![Objeto::Pared, Objeto::Roca, Objeto::Vacio],
+            vec![
+                Objeto::Bomba(true, 1, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+            ],
+        ];
+        let grafo = tablero.grafo_de_bombas();
+
+        assert_eq!(grafo.nodos.len(), 4);
+        assert_eq!(grafo.nodos[&(0, 2)], "S1");
+        assert_eq!(grafo.aristas[&(0, 0)], [(1, 0)].into_iter().collect());
+        // La bomba de (1, 0) llega a (2, 2) gracias al desvío y no atraviesa la roca.
+        assert_eq!(
+            grafo.aristas[&(1, 0)],
+            [(0, 0), (2, 2)].into_iter().collect()
+        );
+        assert!(grafo.aristas[&(0, 2)].is_empty());
+        assert!(grafo.aristas[&(2, 2)].is_empty());
+    }
+
+    #[test]
+    fn test_cadenas_mas_largas() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Bomba(false, 3, Direcciones::ORTOGONALES),
+                Objeto::Desvio(Direccion::Abajo, None),
+            ],
+            vec![Objeto::Pared, Objeto::Roca, Objeto::Vacio],
+            vec![
+                Objeto::Bomba(true, 1, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+            ],
+        ];
+        let grafo = tablero.grafo_de_bombas();
+
+        assert_eq!(grafo.cadena((0, 0)).len(), 3);
+        assert_eq!(grafo.cadena((2, 2)).len(), 1);
+        assert!(grafo.cadena((1, 1)).is_empty());
+        assert_eq!(grafo.cadenas_mas_largas(), (vec![(0, 0), (1, 0)], 3));
+    }
+
+    #[test]
+    fn test_a_dot() {
+        let mut tablero = Tablero::new(2);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Bomba(true, 1, Direcciones::ORTOGONALES),
+            ],
+            vec![Objeto::Vacio, Objeto::Vacio],
+        ];
+
+        let dot = tablero.grafo_de_bombas().a_dot();
+        assert_eq!(
+            dot,
+            "digraph bombas {\n    \"0,0\" [label=\"B1 (0, 0)\\ncadena: 2\"];\n    \"1,0\" [label=\"S1 (1, 0)\\ncadena: 2\"];\n    \"0,0\" -> \"1,0\";\n    \"1,0\" -> \"0,0\";\n}\n"
+        );
+    }
+}
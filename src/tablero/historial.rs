@@ -0,0 +1,381 @@
+use super::model::evento::Evento;
+use super::model::objeto::Objeto;
+use super::Tablero;
+
+/// Representa una modificación hecha sobre el tablero a través del historial.
+#[derive(PartialEq, Debug, Clone)]
+pub enum Accion {
+    /// Detonación de la bomba en `(x, y)`.
+    Detonacion(i32, i32),
+    /// Reemplazo manual del objeto en `(x, y)`.
+    Edicion(i32, i32),
+}
+
+/// Representa el contenido de una celda antes y después de una acción: su ocupante y su
+/// suelo, que cambia cuando un interruptor gira un desvío del suelo.
+#[derive(PartialEq, Debug, Clone)]
+struct CambioDeCelda {
+    x: usize,
+    y: usize,
+    antes: (Objeto, Objeto),
+    despues: (Objeto, Objeto),
+}
+
+/// Representa una acción del historial junto con las celdas que modificó.
+#[derive(PartialEq, Debug, Clone)]
+struct Paso {
+    accion: Accion,
+    cambios: Vec<CambioDeCelda>,
+}
+
+/// Historial de modificaciones de un tablero que permite deshacer, rehacer y volver a
+/// cualquier estado anterior. Cada paso guarda solo las celdas que cambiaron, no una copia de
+/// la cuadrícula.
+#[derive(Debug, Clone)]
+pub struct Historial {
+    tablero: Tablero,
+    pasos: Vec<Paso>,
+    /// Cantidad de pasos aplicados sobre el tablero. Los pasos siguientes se pueden rehacer.
+    posicion: usize,
+}
+
+impl Historial {
+    /// Crea un historial vacío a partir del estado inicial del tablero.
+    pub fn new(tablero: Tablero) -> Historial {
+        Historial {
+            tablero,
+            pasos: Vec::new(),
+            posicion: 0,
+        }
+    }
+
+    /// Devuelve el tablero en su estado actual.
+    pub fn tablero(&self) -> &Tablero {
+        &self.tablero
+    }
+
+    /// Devuelve la cantidad de acciones aplicadas. El estado inicial es la posición `0`.
+    pub fn posicion(&self) -> usize {
+        self.posicion
+    }
+
+    /// Devuelve todas las acciones registradas, incluidas las que se pueden rehacer.
+    pub fn acciones(&self) -> Vec<&Accion> {
+        self.pasos.iter().map(|paso| &paso.accion).collect()
+    }
+
+    /// Detona la bomba en `(x, y)` y registra la detonación. Descarta las acciones que se
+    /// podían rehacer.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve `Err(String)` si en `(x, y)` no hay una bomba; en ese caso no se registra nada.
+    pub fn detonar(&mut self, x: i32, y: i32) -> Result<(), String> {
+        let anterior = self.tablero.clone();
+        self.tablero.detonar(x, y)?;
+
+        let mut cambios: Vec<CambioDeCelda> = Vec::new();
+        for evento in &self.tablero.eventos {
+            let (x, y) = match *evento {
+                Evento::Detonacion(x, y)
+                | Evento::Impacto(x, y, _, _)
+                | Evento::Conmutacion(x, y, _) => (x as usize, y as usize),
+                Evento::Alcance(_, _, _) => continue,
+            };
+            if cambios.iter().any(|c| (c.x, c.y) == (x, y)) {
+                continue;
+            }
+            cambios.push(CambioDeCelda {
+                x,
+                y,
+                antes: contenido(&anterior, x, y),
+                despues: contenido(&self.tablero, x, y),
+            });
+        }
+        self.registrar(Accion::Detonacion(x, y), cambios);
+        Ok(())
+    }
+
+    /// Reemplaza el objeto en `(x, y)` y registra la edición. Descarta las acciones que se
+    /// podían rehacer.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve `Err(String)` si `(x, y)` está fuera del tablero.
+    pub fn editar(&mut self, x: i32, y: i32, objeto: Objeto) -> Result<(), String> {
+        let celda = usize::try_from(y)
+            .ok()
+            .zip(usize::try_from(x).ok())
+            .and_then(|(fila, columna)| {
+                self.tablero
+                    .cuadricula
+                    .get_mut(fila)
+                    .and_then(|f| f.get_mut(columna))
+            })
+            .ok_or_else(|| format!("ERROR: La posición ({}, {}) está fuera del tablero.", x, y))?;
+
+        let antes = std::mem::replace(celda, objeto.clone());
+        self.tablero.eventos.clear();
+        let suelo = self.tablero.suelo_en(x as usize, y as usize).clone();
+        let cambio = CambioDeCelda {
+            x: x as usize,
+            y: y as usize,
+            antes: (antes, suelo.clone()),
+            despues: (objeto, suelo),
+        };
+        self.registrar(Accion::Edicion(x, y), vec![cambio]);
+        Ok(())
+    }
+
+    /// Deshace la última acción aplicada.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve `false` si no había nada para deshacer.
+    pub fn deshacer(&mut self) -> bool {
+        if self.posicion == 0 {
+            return false;
+        }
+        self.posicion -= 1;
+        for cambio in self.pasos[self.posicion].cambios.iter().rev() {
+            restaurar(&mut self.tablero, cambio.x, cambio.y, &cambio.antes);
+        }
+        self.tablero.eventos.clear();
+        true
+    }
+
+    /// Vuelve a aplicar la última acción deshecha.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve `false` si no había nada para rehacer.
+    pub fn rehacer(&mut self) -> bool {
+        if self.posicion == self.pasos.len() {
+            return false;
+        }
+        for cambio in &self.pasos[self.posicion].cambios {
+            restaurar(&mut self.tablero, cambio.x, cambio.y, &cambio.despues);
+        }
+        self.posicion += 1;
+        self.tablero.eventos.clear();
+        true
+    }
+
+    /// Lleva el tablero al estado que tenía luego de `posicion` acciones, deshaciendo o
+    /// rehaciendo las que hagan falta.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve `Err(String)` si `posicion` es mayor a la cantidad de acciones registradas.
+    pub fn ir_a(&mut self, posicion: usize) -> Result<(), String> {
+        if posicion > self.pasos.len() {
+            return Err(format!(
+                "ERROR: El historial tiene {} acciones, no se puede ir a la {}.",
+                self.pasos.len(),
+                posicion
+            ));
+        }
+        while self.posicion > posicion {
+            self.deshacer();
+        }
+        while self.posicion < posicion {
+            self.rehacer();
+        }
+        Ok(())
+    }
+
+    fn registrar(&mut self, accion: Accion, cambios: Vec<CambioDeCelda>) {
+        self.pasos.truncate(self.posicion);
+        self.pasos.push(Paso { accion, cambios });
+        self.posicion += 1;
+    }
+}
+
+/// Devuelve el ocupante y el suelo de la celda `(x, y)`.
+fn contenido(tablero: &Tablero, x: usize, y: usize) -> (Objeto, Objeto) {
+    (
+        tablero.cuadricula[y][x].clone(),
+        tablero.suelo_en(x, y).clone(),
+    )
+}
+
+/// Deja en la celda `(x, y)` el ocupante y el suelo indicados. Solo crea la capa de suelo si
+/// el suelo cambia.
+fn restaurar(tablero: &mut Tablero, x: usize, y: usize, (ocupante, suelo): &(Objeto, Objeto)) {
+    tablero.cuadricula[y][x] = ocupante.clone();
+    if tablero.suelo_en(x, y) != suelo {
+        *tablero.suelo_en_mut(x, y) = suelo.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tablero::leer_tablero;
+    use crate::tablero::model::direccion::{Direccion, Direcciones};
+    use crate::tablero::model::enemigo::TipoDeEnemigo;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_deshacer_y_rehacer_detonacion() {
+        let mut inicial = Tablero::new(3);
+        inicial.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+                Objeto::Vacio,
+            ],
+            vec![Objeto::Pared, Objeto::Roca, Objeto::Vacio],
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+                Objeto::Vacio,
+            ],
+        ];
+        let mut historial = Historial::new(inicial.clone());
+        assert!(historial.detonar(0, 0).is_ok());
+        let detonado = historial.tablero().cuadricula.clone();
+        assert_eq!(detonado[0][0], Objeto::Vacio);
+
+        assert!(historial.deshacer());
+        assert_eq!(historial.tablero().cuadricula, inicial.cuadricula);
+        assert!(!historial.deshacer());
+
+        assert!(historial.rehacer());
+        assert_eq!(historial.tablero().cuadricula, detonado);
+        assert!(!historial.rehacer());
+    }
+
+    #[test]
+    fn test_detonacion_guarda_solo_celdas_modificadas() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+                Objeto::Vacio,
+            ],
+            vec![Objeto::Pared, Objeto::Roca, Objeto::Vacio],
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+                Objeto::Vacio,
+            ],
+        ];
+        let mut historial = Historial::new(tablero);
+        assert!(historial.detonar(0, 0).is_ok());
+        let posiciones: Vec<(usize, usize)> = historial.pasos[0]
+            .cambios
+            .iter()
+            .map(|c| (c.x, c.y))
+            .collect();
+        assert_eq!(posiciones, vec![(0, 0), (1, 0)]);
+    }
+
+    #[test]
+    fn test_nueva_accion_descarta_rehacer() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+                Objeto::Vacio,
+            ],
+            vec![Objeto::Pared, Objeto::Roca, Objeto::Vacio],
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+                Objeto::Vacio,
+            ],
+        ];
+        let mut historial = Historial::new(tablero);
+        assert!(historial.detonar(0, 0).is_ok());
+        assert!(historial.deshacer());
+        assert!(historial.editar(2, 2, Objeto::Roca).is_ok());
+
+        assert_eq!(historial.acciones(), vec![&Accion::Edicion(2, 2)]);
+        assert!(!historial.rehacer());
+        assert_eq!(
+            historial.tablero().cuadricula[0][0],
+            Objeto::Bomba(false, 2, Direcciones::ORTOGONALES)
+        );
+    }
+
+    #[test]
+    fn test_ir_a() {
+        let mut inicial = Tablero::new(3);
+        inicial.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+                Objeto::Vacio,
+            ],
+            vec![Objeto::Pared, Objeto::Roca, Objeto::Vacio],
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+                Objeto::Vacio,
+            ],
+        ];
+        let mut historial = Historial::new(inicial.clone());
+        assert!(historial.editar(2, 0, Objeto::Pared).is_ok());
+        assert!(historial.detonar(0, 0).is_ok());
+        assert!(historial.detonar(0, 2).is_ok());
+        let final_ = historial.tablero().cuadricula.clone();
+
+        assert!(historial.ir_a(0).is_ok());
+        assert_eq!(historial.tablero().cuadricula, inicial.cuadricula);
+        assert!(historial.ir_a(1).is_ok());
+        assert_eq!(historial.tablero().cuadricula[0][2], Objeto::Pared);
+        assert_eq!(
+            historial.tablero().cuadricula[0][0],
+            Objeto::Bomba(false, 2, Direcciones::ORTOGONALES)
+        );
+        assert!(historial.ir_a(3).is_ok());
+        assert_eq!(historial.tablero().cuadricula, final_);
+        assert!(historial.ir_a(4).is_err());
+    }
+
+    #[test]
+    fn test_errores_no_se_registran() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+                Objeto::Vacio,
+            ],
+            vec![Objeto::Pared, Objeto::Roca, Objeto::Vacio],
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+                Objeto::Vacio,
+            ],
+        ];
+        let mut historial = Historial::new(tablero);
+        assert!(historial.detonar(1, 1).is_err());
+        assert!(historial.editar(3, 0, Objeto::Roca).is_err());
+        assert!(historial.editar(-1, 0, Objeto::Roca).is_err());
+        assert!(historial.acciones().is_empty());
+        assert_eq!(historial.posicion(), 0);
+    }
+
+    #[test]
+    fn test_deshacer_restaura_desvio_del_suelo() {
+        let tablero = leer_tablero("B1 I1 F1+DU:1\n_ _ _\n_ _ _\n".as_bytes()).unwrap();
+        let mut historial = Historial::new(tablero.clone());
+        assert!(historial.detonar(0, 0).is_ok());
+        assert_eq!(
+            *historial.tablero().suelo_en(2, 0),
+            Objeto::Desvio(Direccion::Derecha, Some(1))
+        );
+
+        assert!(historial.deshacer());
+        assert_eq!(historial.tablero(), &tablero);
+        assert!(historial.rehacer());
+        assert_eq!(
+            *historial.tablero().suelo_en(2, 0),
+            Objeto::Desvio(Direccion::Derecha, Some(1))
+        );
+    }
+}
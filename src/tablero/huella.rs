@@ -0,0 +1,188 @@
+use super::model::objeto::{convertir_a_simbolo, Objeto};
+use super::Tablero;
+use std::hash::{Hash, Hasher};
+
+/// Dos tableros son iguales si tienen el mismo tamaño, los mismos bordes y los mismos objetos en
+/// cada celda, en ambas capas. Los eventos de la última detonación no forman parte del estado
+/// del tablero, y da igual si un tablero sin suelo tiene la capa de suelo vacía o llena de
+/// `Objeto::Vacio`.
+impl PartialEq for Tablero {
+    fn eq(&self, otro: &Tablero) -> bool {
+        self.tamaño == otro.tamaño
+            && self.toroidal == otro.toroidal
+            && self.cuadricula == otro.cuadricula
+            && suelo_ocupado(self) == suelo_ocupado(otro)
+    }
+}
+
+impl Eq for Tablero {}
+
+impl Hash for Tablero {
+    fn hash<H: Hasher>(&self, estado: &mut H) {
+        self.tamaño.hash(estado);
+        self.toroidal.hash(estado);
+        self.cuadricula.hash(estado);
+        suelo_ocupado(self).hash(estado);
+    }
+}
+
+impl Tablero {
+    /// Devuelve una cadena que identifica el estado del tablero: las dimensiones seguidas de
+    /// las filas separadas por `/`. Los enemigos incluyen, ordenadas, las bombas que ya los
+    /// golpearon, por ejemplo `3x2:B1 F1[0,0] _/W R+DU _`, y las celdas con suelo llevan el
+    /// suelo después de `+`. Los tableros toroidales llevan el prefijo `@`.
+    ///
+    /// A diferencia del `Hash` de la biblioteca estándar, la huella no cambia entre versiones
+    /// del compilador, así que sirve como clave de cachés guardadas en disco.
+    pub fn huella(&self) -> String {
+        let (ancho, alto) = self.dimensiones();
+        let filas: Vec<String> = self
+            .cuadricula
+            .iter()
+            .enumerate()
+            .map(|(y, fila)| {
+                fila.iter()
+                    .enumerate()
+                    .map(|(x, objeto)| match self.suelo_en(x, y) {
+                        Objeto::Vacio => simbolo_con_afectadas(objeto),
+                        suelo => format!(
+                            "{}+{}",
+                            simbolo_con_afectadas(objeto),
+                            convertir_a_simbolo(suelo)
+                        ),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            })
+            .collect();
+        let prefijo = if self.toroidal { "@" } else { "" };
+        format!("{}{}x{}:{}", prefijo, ancho, alto, filas.join("/"))
+    }
+
+    /// Devuelve la huella que no cambia al rotar o reflejar el tablero: la menor de las
+    /// huellas de sus ocho simetrías. Dos tableros tienen la misma huella canónica si uno se
+    /// obtiene girando o reflejando el otro.
+    pub fn huella_canonica(&self) -> String {
+        self.simetrias()
+            .iter()
+            .map(Tablero::huella)
+            .min()
+            .unwrap_or_else(|| self.huella())
+    }
+}
+
+/// Devuelve las posiciones y los objetos de las celdas que tienen suelo.
+fn suelo_ocupado(tablero: &Tablero) -> Vec<((usize, usize), &Objeto)> {
+    let mut ocupado = Vec::new();
+    for (y, fila) in tablero.suelo.iter().enumerate() {
+        for (x, objeto) in fila.iter().enumerate() {
+            if *objeto != Objeto::Vacio {
+                ocupado.push(((x, y), objeto));
+            }
+        }
+    }
+    ocupado
+}
+
+fn simbolo_con_afectadas(objeto: &Objeto) -> String {
+    let simbolo = convertir_a_simbolo(objeto);
+    match objeto {
+        Objeto::Enemigo(_, afectadas, _) if !afectadas.is_empty() => {
+            let mut afectadas: Vec<&(i32, i32)> = afectadas.iter().collect();
+            afectadas.sort();
+            let posiciones: Vec<String> = afectadas
+                .iter()
+                .map(|(x, y)| format!("{},{}", x, y))
+                .collect();
+            format!("{}[{}]", simbolo, posiciones.join(";"))
+        }
+        _ => simbolo,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tablero::leer_tablero;
+    use crate::tablero::model::direccion::{Direccion, Direcciones};
+    use crate::tablero::model::enemigo::TipoDeEnemigo;
+    use crate::tablero::model::evento::Evento;
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn test_huella() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(1, [(0, 0)].into_iter().collect(), TipoDeEnemigo::Comun),
+                Objeto::Desvio(Direccion::Abajo, None),
+            ],
+            vec![Objeto::Pared, Objeto::Roca, Objeto::Vacio],
+        ];
+        assert_eq!(tablero.huella(), "3x2:B1 F1[0,0] DD/W R _");
+    }
+
+    #[test]
+    fn test_igualdad_ignora_eventos() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(1, [(0, 0)].into_iter().collect(), TipoDeEnemigo::Comun),
+                Objeto::Desvio(Direccion::Abajo, None),
+            ],
+            vec![Objeto::Pared, Objeto::Roca, Objeto::Vacio],
+        ];
+        let mut con_eventos = tablero.clone();
+        con_eventos.eventos.push(Evento::Detonacion(0, 0));
+        let mut detonado = tablero.clone();
+        assert!(detonado.detonar(0, 0).is_ok());
+
+        assert_eq!(tablero, con_eventos);
+        assert_ne!(tablero, detonado);
+
+        let mut repetidos = HashMap::new();
+        for estado in [&tablero, &tablero.clone(), &detonado] {
+            *repetidos.entry(estado.clone()).or_insert(0) += 1;
+        }
+        assert_eq!(repetidos.get(&tablero), Some(&2));
+        assert_eq!(repetidos.len(), 2);
+    }
+
+    #[test]
+    fn test_huella_canonica_invariante_a_simetrias() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(1, [(0, 0)].into_iter().collect(), TipoDeEnemigo::Comun),
+                Objeto::Desvio(Direccion::Abajo, None),
+            ],
+            vec![Objeto::Pared, Objeto::Roca, Objeto::Vacio],
+        ];
+        let canonica = tablero.huella_canonica();
+        for simetria in tablero.simetrias() {
+            assert_eq!(simetria.huella_canonica(), canonica);
+        }
+
+        let mut distinto = tablero.clone();
+        distinto.cuadricula[1][1] = Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun);
+        assert_ne!(distinto.huella_canonica(), canonica);
+    }
+
+    #[test]
+    fn test_huella_e_igualdad_con_suelo() {
+        let tablero = leer_tablero("B1+DR _\n_ _\n".as_bytes()).unwrap();
+        assert_eq!(tablero.huella(), "2x2:B1+DR _/_ _");
+
+        // Una capa de suelo sin objetos equivale a no tener suelo.
+        let mut sin_suelo = leer_tablero("B1 _\n_ _\n".as_bytes()).unwrap();
+        let mut con_suelo_vacio = sin_suelo.clone();
+        *con_suelo_vacio.suelo_en_mut(1, 1) = Objeto::Vacio;
+        assert_eq!(sin_suelo, con_suelo_vacio);
+
+        *sin_suelo.suelo_en_mut(0, 0) = Objeto::Desvio(Direccion::Derecha, None);
+        assert_eq!(sin_suelo, tablero);
+    }
+}
@@ -0,0 +1,120 @@
+use super::model::evento::Evento;
+use super::model::objeto::Objeto;
+use super::Tablero;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Devuelve el objeto en que se convierte `objeto` cuando se activa un interruptor enlazado:
+/// las compuertas se abren o se cierran y los desvíos giran 90° en sentido horario.
+///
+/// # Devuelve
+///
+/// Devuelve `None` si el objeto no se puede enlazar a un interruptor.
+pub fn conmutar(objeto: &Objeto) -> Option<Objeto> {
+    match objeto {
+        Objeto::Compuerta(id, abierta) => Some(Objeto::Compuerta(*id, !abierta)),
+        Objeto::Desvio(direccion, Some(id)) => {
+            Some(Objeto::Desvio(direccion.rotada_horario(), Some(*id)))
+        }
+        _ => None,
+    }
+}
+
+/// Devuelve el identificador de los interruptores a los que está enlazado `objeto`, si tiene.
+fn enlace(objeto: &Objeto) -> Option<u32> {
+    match objeto {
+        Objeto::Compuerta(id, _) | Objeto::Desvio(_, Some(id)) => Some(*id),
+        _ => None,
+    }
+}
+
+impl Tablero {
+    /// Devuelve las posiciones `(x, y)` de las compuertas y los desvíos enlazados agrupadas
+    /// por el identificador de sus interruptores, recorriendo la cuadrícula por filas. Incluye
+    /// los desvíos que están en el suelo.
+    pub fn enlazados(&self) -> BTreeMap<u32, Vec<(i32, i32)>> {
+        let mut enlazados: BTreeMap<u32, Vec<(i32, i32)>> = BTreeMap::new();
+        for ((x, y), objeto) in self.objetos_con_suelo() {
+            if let Some(id) = enlace(objeto) {
+                enlazados.entry(id).or_default().push((x as i32, y as i32));
+            }
+        }
+        enlazados
+    }
+
+    /// Devuelve los identificadores de todos los interruptores del tablero, incluidos los que
+    /// están en el suelo.
+    pub fn interruptores(&self) -> BTreeSet<u32> {
+        self.objetos_con_suelo()
+            .into_iter()
+            .filter_map(|(_, objeto)| match objeto {
+                Objeto::Interruptor(id) => Some(*id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Conmuta, en el orden de la cuadrícula, todos los objetos enlazados al interruptor `id`
+    /// ubicado en `(x, y)`, y registra un evento por cada uno. Como el cambio es inmediato,
+    /// los rayos que se procesan después ya encuentran las compuertas abiertas o cerradas.
+    pub(super) fn activar_interruptor(&mut self, x: usize, y: usize, id: u32) {
+        let posiciones = self.enlazados().remove(&id).unwrap_or_default();
+        for (enlazado_x, enlazado_y) in posiciones {
+            if self.conmutar_en(enlazado_x as usize, enlazado_y as usize) {
+                self.eventos.push(Evento::Conmutacion(
+                    enlazado_x,
+                    enlazado_y,
+                    (x as i32, y as i32),
+                ));
+            }
+        }
+    }
+
+    /// Conmuta el objeto enlazado de la celda `(x, y)`, esté en la cuadrícula o en el suelo.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve `false` si la celda no tiene ningún objeto que se pueda conmutar.
+    pub(super) fn conmutar_en(&mut self, x: usize, y: usize) -> bool {
+        if let Some(conmutado) = conmutar(&self.cuadricula[y][x]) {
+            self.cuadricula[y][x] = conmutado;
+        } else if let Some(conmutado) = conmutar(self.suelo_en(x, y)) {
+            *self.suelo_en_mut(x, y) = conmutado;
+        } else {
+            return false;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tablero::leer_tablero;
+    use crate::tablero::model::direccion::Direccion;
+
+    #[test]
+    fn test_conmutar() {
+        assert_eq!(
+            conmutar(&Objeto::Compuerta(1, false)),
+            Some(Objeto::Compuerta(1, true))
+        );
+        assert_eq!(
+            conmutar(&Objeto::Desvio(Direccion::Arriba, Some(2))),
+            Some(Objeto::Desvio(Direccion::Derecha, Some(2)))
+        );
+        assert_eq!(conmutar(&Objeto::Desvio(Direccion::Arriba, None)), None);
+        assert_eq!(conmutar(&Objeto::Pared), None);
+    }
+
+    #[test]
+    fn test_enlazados_e_interruptores() {
+        let tablero = leer_tablero("I1 C1 _\nDU:1 C2:A I3\n_ _ _\n".as_bytes()).unwrap();
+        assert_eq!(
+            tablero.enlazados(),
+            [(1, vec![(1, 0), (0, 1)]), (2, vec![(1, 1)])]
+                .into_iter()
+                .collect()
+        );
+        assert_eq!(tablero.interruptores(), [1, 3].into_iter().collect());
+    }
+}
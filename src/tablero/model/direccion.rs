@@ -1,4 +1,4 @@
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 /// Enumeración que representa las direcciones posibles.
 pub enum Direccion {
     /// Dirección hacia arriba.
@@ -9,4 +9,244 @@ pub enum Direccion {
     Izquierda,
     /// Dirección hacia la derecha.
     Derecha,
+    /// Dirección diagonal hacia arriba y a la izquierda.
+    ArribaIzquierda,
+    /// Dirección diagonal hacia arriba y a la derecha.
+    ArribaDerecha,
+    /// Dirección diagonal hacia abajo y a la izquierda.
+    AbajoIzquierda,
+    /// Dirección diagonal hacia abajo y a la derecha.
+    AbajoDerecha,
+}
+
+impl Direccion {
+    /// Las ocho direcciones, en el orden en que se disparan los rayos de una bomba.
+    pub const TODAS: [Direccion; 8] = [
+        Direccion::Arriba,
+        Direccion::Abajo,
+        Direccion::Izquierda,
+        Direccion::Derecha,
+        Direccion::ArribaIzquierda,
+        Direccion::ArribaDerecha,
+        Direccion::AbajoIzquierda,
+        Direccion::AbajoDerecha,
+    ];
+
+    /// Devuelve el nombre de la dirección en minúsculas, para mostrarlo en informes.
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            Direccion::Arriba => "arriba",
+            Direccion::Abajo => "abajo",
+            Direccion::Izquierda => "izquierda",
+            Direccion::Derecha => "derecha",
+            Direccion::ArribaIzquierda => "arriba-izquierda",
+            Direccion::ArribaDerecha => "arriba-derecha",
+            Direccion::AbajoIzquierda => "abajo-izquierda",
+            Direccion::AbajoDerecha => "abajo-derecha",
+        }
+    }
+
+    /// Devuelve cuánto cambian la columna y la fila al avanzar un paso en esta dirección.
+    pub fn desplazamiento(&self) -> (i32, i32) {
+        match self {
+            Direccion::Arriba => (0, -1),
+            Direccion::Abajo => (0, 1),
+            Direccion::Izquierda => (-1, 0),
+            Direccion::Derecha => (1, 0),
+            Direccion::ArribaIzquierda => (-1, -1),
+            Direccion::ArribaDerecha => (1, -1),
+            Direccion::AbajoIzquierda => (-1, 1),
+            Direccion::AbajoDerecha => (1, 1),
+        }
+    }
+
+    /// Devuelve la dirección girada 90° en sentido horario.
+    pub fn rotada_horario(&self) -> Direccion {
+        match self {
+            Direccion::Arriba => Direccion::Derecha,
+            Direccion::Derecha => Direccion::Abajo,
+            Direccion::Abajo => Direccion::Izquierda,
+            Direccion::Izquierda => Direccion::Arriba,
+            Direccion::ArribaIzquierda => Direccion::ArribaDerecha,
+            Direccion::ArribaDerecha => Direccion::AbajoDerecha,
+            Direccion::AbajoDerecha => Direccion::AbajoIzquierda,
+            Direccion::AbajoIzquierda => Direccion::ArribaIzquierda,
+        }
+    }
+
+    /// Devuelve la dirección reflejada respecto de un eje vertical: izquierda y derecha se
+    /// intercambian.
+    pub fn espejada_horizontal(&self) -> Direccion {
+        match self {
+            Direccion::Izquierda => Direccion::Derecha,
+            Direccion::Derecha => Direccion::Izquierda,
+            Direccion::ArribaIzquierda => Direccion::ArribaDerecha,
+            Direccion::ArribaDerecha => Direccion::ArribaIzquierda,
+            Direccion::AbajoIzquierda => Direccion::AbajoDerecha,
+            Direccion::AbajoDerecha => Direccion::AbajoIzquierda,
+            otra => otra.clone(),
+        }
+    }
+
+    /// Devuelve la dirección reflejada respecto de un eje horizontal: arriba y abajo se
+    /// intercambian.
+    pub fn espejada_vertical(&self) -> Direccion {
+        match self {
+            Direccion::Arriba => Direccion::Abajo,
+            Direccion::Abajo => Direccion::Arriba,
+            Direccion::ArribaIzquierda => Direccion::AbajoIzquierda,
+            Direccion::ArribaDerecha => Direccion::AbajoDerecha,
+            Direccion::AbajoIzquierda => Direccion::ArribaIzquierda,
+            Direccion::AbajoDerecha => Direccion::ArribaDerecha,
+            otra => otra.clone(),
+        }
+    }
+
+    fn bit(&self) -> u8 {
+        let indice = Direccion::TODAS
+            .iter()
+            .position(|direccion| direccion == self)
+            .unwrap_or(0);
+        1 << indice
+    }
+}
+
+/// Conjunto de direcciones en las que dispara una bomba.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct Direcciones(u8);
+
+impl Direcciones {
+    /// Arriba, abajo, izquierda y derecha: la explosión en cruz de las bombas comunes.
+    pub const ORTOGONALES: Direcciones = Direcciones(0b0000_1111);
+    /// Las cuatro diagonales.
+    pub const DIAGONALES: Direcciones = Direcciones(0b1111_0000);
+    /// Las ocho direcciones.
+    pub const TODAS: Direcciones = Direcciones(0b1111_1111);
+    /// Izquierda y derecha: una bomba de línea horizontal.
+    pub const HORIZONTALES: Direcciones = Direcciones(0b0000_1100);
+    /// Arriba y abajo: una bomba de línea vertical.
+    pub const VERTICALES: Direcciones = Direcciones(0b0000_0011);
+
+    /// Crea el conjunto formado por `direcciones`.
+    pub fn de(direcciones: &[Direccion]) -> Direcciones {
+        Direcciones(
+            direcciones
+                .iter()
+                .fold(0, |bits, direccion| bits | direccion.bit()),
+        )
+    }
+
+    /// Indica si `direccion` pertenece al conjunto.
+    pub fn contiene(&self, direccion: &Direccion) -> bool {
+        self.0 & direccion.bit() != 0
+    }
+
+    /// Devuelve las direcciones del conjunto en el orden de `Direccion::TODAS`.
+    pub fn direcciones(&self) -> Vec<Direccion> {
+        Direccion::TODAS
+            .iter()
+            .filter(|direccion| self.contiene(direccion))
+            .cloned()
+            .collect()
+    }
+
+    /// Devuelve el conjunto que resulta de transformar cada una de sus direcciones.
+    pub fn mapear(&self, transformar: impl Fn(&Direccion) -> Direccion) -> Direcciones {
+        let transformadas: Vec<Direccion> = self.direcciones().iter().map(transformar).collect();
+        Direcciones::de(&transformadas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotada_horario() {
+        let mut direccion = Direccion::Arriba;
+        let mut recorrido = Vec::new();
+        for _ in 0..4 {
+            direccion = direccion.rotada_horario();
+            recorrido.push(direccion.clone());
+        }
+        assert_eq!(
+            recorrido,
+            vec![
+                Direccion::Derecha,
+                Direccion::Abajo,
+                Direccion::Izquierda,
+                Direccion::Arriba
+            ]
+        );
+        assert_eq!(
+            Direccion::ArribaIzquierda.rotada_horario(),
+            Direccion::ArribaDerecha
+        );
+    }
+
+    #[test]
+    fn test_espejada_horizontal() {
+        assert_eq!(
+            Direccion::Izquierda.espejada_horizontal(),
+            Direccion::Derecha
+        );
+        assert_eq!(Direccion::Arriba.espejada_horizontal(), Direccion::Arriba);
+        assert_eq!(
+            Direccion::AbajoDerecha.espejada_horizontal(),
+            Direccion::AbajoIzquierda
+        );
+    }
+
+    #[test]
+    fn test_espejada_vertical() {
+        assert_eq!(Direccion::Arriba.espejada_vertical(), Direccion::Abajo);
+        assert_eq!(Direccion::Derecha.espejada_vertical(), Direccion::Derecha);
+        assert_eq!(
+            Direccion::ArribaDerecha.espejada_vertical(),
+            Direccion::AbajoDerecha
+        );
+    }
+
+    #[test]
+    fn test_desplazamiento_de_las_transformaciones() {
+        // Girar o reflejar la dirección equivale a girar o reflejar su desplazamiento.
+        for direccion in Direccion::TODAS {
+            let (dx, dy) = direccion.desplazamiento();
+            assert_eq!(direccion.rotada_horario().desplazamiento(), (-dy, dx));
+            assert_eq!(direccion.espejada_horizontal().desplazamiento(), (-dx, dy));
+            assert_eq!(direccion.espejada_vertical().desplazamiento(), (dx, -dy));
+        }
+    }
+
+    #[test]
+    fn test_direcciones() {
+        assert_eq!(
+            Direcciones::ORTOGONALES.direcciones(),
+            Direccion::TODAS[..4].to_vec()
+        );
+        assert!(Direcciones::DIAGONALES.contiene(&Direccion::AbajoIzquierda));
+        assert!(!Direcciones::DIAGONALES.contiene(&Direccion::Abajo));
+        assert_eq!(Direcciones::TODAS.direcciones().len(), 8);
+        assert_eq!(
+            Direcciones::DIAGONALES.mapear(Direccion::rotada_horario),
+            Direcciones::DIAGONALES
+        );
+        assert_eq!(
+            Direcciones::HORIZONTALES.mapear(Direccion::rotada_horario),
+            Direcciones::VERTICALES
+        );
+    }
+
+    #[test]
+    fn test_direcciones_de() {
+        assert_eq!(
+            Direcciones::de(&[Direccion::Izquierda, Direccion::Derecha]),
+            Direcciones::HORIZONTALES
+        );
+        assert_eq!(
+            Direcciones::de(&[Direccion::Arriba]).direcciones(),
+            vec![Direccion::Arriba]
+        );
+        assert!(Direcciones::de(&[]).direcciones().is_empty());
+    }
 }
@@ -0,0 +1,26 @@
+/// Enumeración que representa los tipos de enemigo, que difieren en cómo reciben el daño.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum TipoDeEnemigo {
+    /// Pierde una vida por cada bomba que lo alcanza, aunque la misma bomba lo alcance varias
+    /// veces.
+    Comun,
+    /// Solo lo dañan las bombas de traspaso; las demás explosiones no le quitan vida.
+    Blindado,
+    /// Pierde una vida cada vez que un rayo lo alcanza, aunque venga de una bomba que ya lo
+    /// golpeó.
+    Vulnerable,
+    /// Al morir explota como una bomba común con el alcance indicado.
+    Explosivo(i32),
+}
+
+impl TipoDeEnemigo {
+    /// Devuelve el nombre del tipo en minúsculas, para mostrarlo en informes.
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            TipoDeEnemigo::Comun => "común",
+            TipoDeEnemigo::Blindado => "blindado",
+            TipoDeEnemigo::Vulnerable => "vulnerable",
+            TipoDeEnemigo::Explosivo(_) => "explosivo",
+        }
+    }
+}
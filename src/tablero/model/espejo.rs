@@ -0,0 +1,94 @@
+use super::direccion::Direccion;
+
+/// Enumeración que representa la orientación de un espejo, que refleja los rayos según la
+/// dirección en la que llegan.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Espejo {
+    /// Espejo `/`: un rayo que viaja hacia la derecha sale hacia arriba.
+    Barra,
+    /// Espejo `\`: un rayo que viaja hacia la derecha sale hacia abajo.
+    BarraInvertida,
+}
+
+impl Espejo {
+    /// Devuelve la dirección en la que sale un rayo que llega al espejo viajando en `entrante`.
+    /// Los rayos diagonales perpendiculares al espejo vuelven por donde vinieron y los paralelos
+    /// siguen de largo.
+    pub fn reflejar(&self, entrante: &Direccion) -> Direccion {
+        let (dx, dy) = entrante.desplazamiento();
+        let reflejado = match self {
+            Espejo::Barra => (-dy, -dx),
+            Espejo::BarraInvertida => (dy, dx),
+        };
+        Direccion::TODAS
+            .into_iter()
+            .find(|direccion| direccion.desplazamiento() == reflejado)
+            .unwrap_or_else(|| entrante.clone())
+    }
+
+    /// Devuelve el espejo que resulta de girar o reflejar el tablero con la transformación de
+    /// direcciones `transformar`: el que refleja las direcciones transformadas igual que este
+    /// refleja las originales.
+    pub fn transformado(&self, transformar: impl Fn(&Direccion) -> Direccion) -> Espejo {
+        let entrante = transformar(&Direccion::Derecha);
+        let esperado = transformar(&self.reflejar(&Direccion::Derecha));
+        if Espejo::Barra.reflejar(&entrante) == esperado {
+            Espejo::Barra
+        } else {
+            Espejo::BarraInvertida
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reflejar() {
+        assert_eq!(
+            Espejo::Barra.reflejar(&Direccion::Derecha),
+            Direccion::Arriba
+        );
+        assert_eq!(
+            Espejo::Barra.reflejar(&Direccion::Abajo),
+            Direccion::Izquierda
+        );
+        assert_eq!(
+            Espejo::BarraInvertida.reflejar(&Direccion::Derecha),
+            Direccion::Abajo
+        );
+        assert_eq!(
+            Espejo::BarraInvertida.reflejar(&Direccion::Arriba),
+            Direccion::Izquierda
+        );
+        assert_eq!(
+            Espejo::Barra.reflejar(&Direccion::AbajoDerecha),
+            Direccion::ArribaIzquierda
+        );
+        assert_eq!(
+            Espejo::Barra.reflejar(&Direccion::ArribaDerecha),
+            Direccion::ArribaDerecha
+        );
+    }
+
+    #[test]
+    fn test_transformado() {
+        assert_eq!(
+            Espejo::Barra.transformado(Direccion::rotada_horario),
+            Espejo::BarraInvertida
+        );
+        assert_eq!(
+            Espejo::Barra.transformado(Direccion::espejada_horizontal),
+            Espejo::BarraInvertida
+        );
+        assert_eq!(
+            Espejo::BarraInvertida.transformado(Direccion::espejada_vertical),
+            Espejo::Barra
+        );
+        assert_eq!(
+            Espejo::Barra.transformado(|direccion| direccion.clone()),
+            Espejo::Barra
+        );
+    }
+}
@@ -0,0 +1,14 @@
+/// Enumeración que representa los sucesos que produce una explosión, en el orden en que ocurren.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub enum Evento {
+    /// Explota la bomba ubicada en la posición `(x, y)`.
+    Detonacion(i32, i32),
+    /// El rayo de la bomba de origen alcanza la posición `(x, y)`.
+    Alcance(i32, i32, (i32, i32)),
+    /// El rayo de la bomba de origen golpea al enemigo de la posición `(x, y)`, que queda con
+    /// la vida indicada (0 si fue eliminado).
+    Impacto(i32, i32, (i32, i32), i32),
+    /// El interruptor de la posición de origen, alcanzado por un rayo, abre o cierra la
+    /// compuerta o gira el desvío de la posición `(x, y)`.
+    Conmutacion(i32, i32, (i32, i32)),
+}
@@ -0,0 +1,88 @@
+/// Enumeración que representa la forma de la explosión de una bomba de área.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Forma {
+    /// Alcanza las celdas a distancia de Chebyshev menor o igual al alcance, incluidas las
+    /// diagonales: un cuadrado centrado en la bomba.
+    Cuadrado,
+    /// Alcanza las celdas a distancia de Manhattan menor o igual al alcance: un rombo centrado
+    /// en la bomba.
+    Rombo,
+}
+
+impl Forma {
+    /// Devuelve el nombre de la forma en minúsculas, para mostrarlo en informes.
+    pub fn nombre(&self) -> &'static str {
+        match self {
+            Forma::Cuadrado => "cuadrado",
+            Forma::Rombo => "rombo",
+        }
+    }
+
+    /// Devuelve la distancia entre el centro y una celda desplazada `(dx, dy)` según la forma.
+    pub fn distancia(&self, dx: i32, dy: i32) -> i32 {
+        match self {
+            Forma::Cuadrado => dx.abs().max(dy.abs()),
+            Forma::Rombo => dx.abs() + dy.abs(),
+        }
+    }
+
+    /// Devuelve la mayor distancia, según la forma, entre dos celdas de un tablero de
+    /// `tamaño` x `tamaño`: la que separa dos esquinas opuestas.
+    pub fn distancia_maxima(&self, tamaño: i32) -> i32 {
+        let lado = (tamaño - 1).max(0);
+        self.distancia(lado, lado)
+    }
+
+    /// Devuelve los desplazamientos `(dx, dy)` de las celdas que cubre una explosión de la
+    /// forma con el alcance indicado, sin incluir el centro.
+    ///
+    /// # Devuelve
+    ///
+    /// Los desplazamientos ordenados de adentro hacia afuera y, a igual distancia, por fila y
+    /// columna, que es el orden en que la explosión alcanza las celdas.
+    pub fn desplazamientos(&self, alcance: i32) -> Vec<(i32, i32)> {
+        let mut desplazamientos = Vec::new();
+        for dy in -alcance..=alcance {
+            for dx in -alcance..=alcance {
+                let distancia = self.distancia(dx, dy);
+                if distancia > 0 && distancia <= alcance {
+                    desplazamientos.push((dx, dy));
+                }
+            }
+        }
+        desplazamientos.sort_by_key(|&(dx, dy)| (self.distancia(dx, dy), dy, dx));
+        desplazamientos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_desplazamientos_cuadrado() {
+        let desplazamientos = Forma::Cuadrado.desplazamientos(1);
+        assert_eq!(desplazamientos.len(), 8);
+        assert!(desplazamientos.contains(&(1, 1)));
+        assert_eq!(Forma::Cuadrado.desplazamientos(2).len(), 24);
+    }
+
+    #[test]
+    fn test_desplazamientos_rombo() {
+        assert_eq!(
+            Forma::Rombo.desplazamientos(1),
+            vec![(0, -1), (-1, 0), (1, 0), (0, 1)]
+        );
+        let desplazamientos = Forma::Rombo.desplazamientos(2);
+        assert_eq!(desplazamientos.len(), 12);
+        assert!(!desplazamientos.contains(&(2, 1)));
+        assert_eq!(desplazamientos[4], (0, -2));
+    }
+
+    #[test]
+    fn test_distancia_maxima() {
+        assert_eq!(Forma::Cuadrado.distancia_maxima(3), 2);
+        assert_eq!(Forma::Rombo.distancia_maxima(3), 4);
+        assert_eq!(Forma::Rombo.distancia_maxima(0), 0);
+    }
+}
@@ -1,2 +1,6 @@
 pub mod direccion;
+pub mod enemigo;
+pub mod espejo;
+pub mod evento;
+pub mod forma;
 pub mod objeto;
@@ -1,23 +1,84 @@
-use super::direccion::Direccion;
+use super::direccion::{Direccion, Direcciones};
+use super::enemigo::TipoDeEnemigo;
+use super::espejo::Espejo;
+use super::forma::Forma;
 use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
 
 /// Enumeración que representa los diferentes tipos de objetos en el juego.
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Debug, Clone)]
 pub enum Objeto {
-    /// Representa un enemigo con una cantidad de vida y las bombas afectadas.
-    Enemigo(i32, HashSet<(i32, i32)>),
-    /// Representa una bomba, con una indicación de si es de traspaso y su alcance.
-    Bomba(bool, i32),
+    /// Representa un enemigo con una cantidad de vida, las bombas afectadas y su tipo.
+    Enemigo(i32, HashSet<(i32, i32)>, TipoDeEnemigo),
+    /// Representa una bomba, con una indicación de si es de traspaso, su alcance y las
+    /// direcciones en las que dispara.
+    Bomba(bool, i32, Direcciones),
+    /// Representa una bomba de área, con la forma de su explosión, su alcance y una indicación
+    /// de si las paredes la ocluyen: si es `true`, solo alcanza las celdas que ve en línea recta
+    /// sin paredes en el medio.
+    BombaDeArea(Forma, i32, bool),
     /// Representa una roca en el tablero.
     Roca,
     /// Representa una pared en el tablero.
     Pared,
-    /// Representa un desvío con una dirección específica.
-    Desvio(Direccion),
+    /// Representa un desvío con una dirección específica y, opcionalmente, el identificador
+    /// de los interruptores que lo hacen girar.
+    Desvio(Direccion, Option<u32>),
+    /// Representa un espejo, que refleja los rayos 90° según la dirección en la que llegan.
+    Espejo(Espejo),
+    /// Representa un divisor, que parte el rayo que llega en dos rayos perpendiculares.
+    Divisor,
+    /// Representa un portal con su identificador. Un rayo que entra a un portal sale por el
+    /// otro portal con el mismo identificador, en la misma dirección.
+    Portal(u32),
+    /// Representa un interruptor con su identificador. Cada vez que lo alcanza un rayo abre o
+    /// cierra las compuertas y gira los desvíos enlazados con el mismo identificador.
+    Interruptor(u32),
+    /// Representa una compuerta con el identificador de sus interruptores y una indicación de
+    /// si está abierta. Cerrada detiene los rayos como una pared.
+    Compuerta(u32, bool),
     /// Representa una casilla vacía en el tablero.
     Vacio,
 }
 
+/// `HashSet` no implementa `Hash`, así que las bombas que golpearon a un enemigo se combinan
+/// ordenadas para que el resultado no dependa del orden de inserción.
+impl Hash for Objeto {
+    fn hash<H: Hasher>(&self, estado: &mut H) {
+        std::mem::discriminant(self).hash(estado);
+        match self {
+            Objeto::Enemigo(vida, afectadas, tipo) => {
+                vida.hash(estado);
+                let mut afectadas: Vec<&(i32, i32)> = afectadas.iter().collect();
+                afectadas.sort();
+                afectadas.hash(estado);
+                tipo.hash(estado);
+            }
+            Objeto::Bomba(traspaso, alcance, direcciones) => {
+                traspaso.hash(estado);
+                alcance.hash(estado);
+                direcciones.hash(estado);
+            }
+            Objeto::BombaDeArea(forma, alcance, ocluida) => {
+                forma.hash(estado);
+                alcance.hash(estado);
+                ocluida.hash(estado);
+            }
+            Objeto::Desvio(direccion, enlace) => {
+                direccion.hash(estado);
+                enlace.hash(estado);
+            }
+            Objeto::Espejo(espejo) => espejo.hash(estado),
+            Objeto::Portal(id) | Objeto::Interruptor(id) => id.hash(estado),
+            Objeto::Compuerta(id, abierta) => {
+                id.hash(estado);
+                abierta.hash(estado);
+            }
+            Objeto::Roca | Objeto::Pared | Objeto::Divisor | Objeto::Vacio => {}
+        }
+    }
+}
+
 /// Convierte un símbolo en un objeto del juego.
 ///
 /// # Argumentos
@@ -31,18 +92,136 @@ pub fn convertir_simbolos(simbolo: &str) -> Result<Objeto, &'static str> {
         'F' => convertir_enemigo(simbolo),
         'B' => convertir_bomba(simbolo),
         'S' => convertir_bomba_traspaso(simbolo),
+        'A' => convertir_bomba_de_area(simbolo),
         'R' => Ok(Objeto::Roca),
         'W' => Ok(Objeto::Pared),
         'D' => convertir_desvio(simbolo),
+        'P' => convertir_portal(simbolo),
+        'I' => convertir_interruptor(simbolo),
+        'C' => convertir_compuerta(simbolo),
+        'Y' => Ok(Objeto::Divisor),
+        '/' => Ok(Objeto::Espejo(Espejo::Barra)),
+        '\\' => Ok(Objeto::Espejo(Espejo::BarraInvertida)),
         '_' => Ok(Objeto::Vacio),
         _ => Err("Símbolo no válido en el laberinto"),
     }
 }
 
+/// Convierte un objeto del juego en el símbolo que lo representa en los archivos de tablero.
+///
+/// # Argumentos
+///
+/// * `objeto`: Una referencia al `Objeto` a convertir.
+///
+pub fn convertir_a_simbolo(objeto: &Objeto) -> String {
+    match objeto {
+        Objeto::Enemigo(vida, _, tipo) => format!("F{}{}", vida, sufijo_de_enemigo(tipo)),
+        Objeto::Bomba(traspaso, alcance, direcciones) => format!(
+            "{}{}{}",
+            if *traspaso { "S" } else { "B" },
+            alcance,
+            sufijo_de_direcciones(direcciones)
+        ),
+        Objeto::BombaDeArea(forma, alcance, ocluida) => format!(
+            "A{}{}{}",
+            codigo_de_forma(forma),
+            alcance,
+            if *ocluida { "" } else { ":T" }
+        ),
+        Objeto::Roca => "R".to_string(),
+        Objeto::Pared => "W".to_string(),
+        Objeto::Desvio(direccion, None) => format!("D{}", codigo_de_direccion(direccion)),
+        Objeto::Desvio(direccion, Some(enlace)) => {
+            format!("D{}:{}", codigo_de_direccion(direccion), enlace)
+        }
+        Objeto::Espejo(Espejo::Barra) => "/".to_string(),
+        Objeto::Espejo(Espejo::BarraInvertida) => "\\".to_string(),
+        Objeto::Divisor => "Y".to_string(),
+        Objeto::Portal(id) => format!("P{}", id),
+        Objeto::Interruptor(id) => format!("I{}", id),
+        Objeto::Compuerta(id, abierta) => format!("C{}{}", id, if *abierta { ":A" } else { "" }),
+        Objeto::Vacio => "_".to_string(),
+    }
+}
+
+/// Indica si el objeto puede ir en la capa de suelo de una celda: los objetos que guían o
+/// accionan los rayos sin detenerlos ni recibir daño.
+pub fn es_de_suelo(objeto: &Objeto) -> bool {
+    matches!(
+        objeto,
+        Objeto::Desvio(_, _)
+            | Objeto::Espejo(_)
+            | Objeto::Divisor
+            | Objeto::Portal(_)
+            | Objeto::Interruptor(_)
+    )
+}
+
+/// Indica si el objeto puede ocupar una celda que tiene suelo: enemigos, bombas y rocas, o
+/// nada si el ocupante ya no está.
+pub fn puede_ocupar_suelo(objeto: &Objeto) -> bool {
+    matches!(
+        objeto,
+        Objeto::Enemigo(_, _, _)
+            | Objeto::Bomba(_, _, _)
+            | Objeto::BombaDeArea(_, _, _)
+            | Objeto::Roca
+            | Objeto::Vacio
+    )
+}
+
+/// Convierte el símbolo de una celda en su ocupante y su suelo. Una celda apilada se escribe
+/// `OCUPANTE+SUELO`, por ejemplo `B2+DR` para una bomba sobre un desvío; las demás celdas no
+/// tienen suelo y su suelo es `Objeto::Vacio`. Una celda sin ocupante, como `_+DR`, es lo
+/// mismo que la baldosa sola: `DR`.
+///
+/// # Argumentos
+///
+/// * `simbolo`: Un `&str` que contiene el símbolo de la celda.
+///
+pub fn convertir_celda(simbolo: &str) -> Result<(Objeto, Objeto), &'static str> {
+    let Some((ocupante, suelo)) = simbolo.split_once('+') else {
+        return Ok((convertir_simbolos(simbolo)?, Objeto::Vacio));
+    };
+    let ocupante = convertir_simbolos(ocupante)?;
+    let suelo = convertir_simbolos(suelo)?;
+    if !es_de_suelo(&suelo) {
+        return Err("Solo desvíos, espejos, divisores, portales e interruptores van en el suelo");
+    }
+    if !puede_ocupar_suelo(&ocupante) {
+        return Err("Solo enemigos, bombas y rocas pueden estar sobre el suelo");
+    }
+    if ocupante == Objeto::Vacio {
+        return Ok((suelo, Objeto::Vacio));
+    }
+    Ok((ocupante, suelo))
+}
+
+/// Convierte el ocupante y el suelo de una celda en el símbolo que la representa: el del
+/// ocupante, seguido de `+` y el del suelo si la celda tiene suelo. Si no hay ocupante, el
+/// símbolo es solo el del suelo.
+pub fn convertir_celda_a_simbolo(ocupante: &Objeto, suelo: &Objeto) -> String {
+    match (ocupante, suelo) {
+        (_, Objeto::Vacio) => convertir_a_simbolo(ocupante),
+        (Objeto::Vacio, _) => convertir_a_simbolo(suelo),
+        _ => format!(
+            "{}+{}",
+            convertir_a_simbolo(ocupante),
+            convertir_a_simbolo(suelo)
+        ),
+    }
+}
+
+/// Convierte un enemigo: `F` y su vida, seguidos opcionalmente del tipo: `:A` para blindado,
+/// `:V` para vulnerable o `:E` y un alcance para explosivo, por ejemplo `F2:E1`.
 fn convertir_enemigo(simbolo: &str) -> Result<Objeto, &'static str> {
-    if let Some(vida) = simbolo.chars().last().and_then(|c| c.to_digit(10)) {
+    let (vida_str, tipo) = match simbolo.split_once(':') {
+        None => (simbolo, TipoDeEnemigo::Comun),
+        Some((vida_str, sufijo)) => (vida_str, convertir_tipo_de_enemigo(sufijo)?),
+    };
+    if let Some(vida) = vida_str.chars().last().and_then(|c| c.to_digit(10)) {
         if (1..=2).contains(&vida) {
-            Ok(Objeto::Enemigo(vida as i32, HashSet::new()))
+            Ok(Objeto::Enemigo(vida as i32, HashSet::new(), tipo))
         } else {
             Err("Valor de vida de enemigo no válido")
         }
@@ -51,11 +230,31 @@ fn convertir_enemigo(simbolo: &str) -> Result<Objeto, &'static str> {
     }
 }
 
+fn convertir_tipo_de_enemigo(sufijo: &str) -> Result<TipoDeEnemigo, &'static str> {
+    match sufijo {
+        "A" => Ok(TipoDeEnemigo::Blindado),
+        "V" => Ok(TipoDeEnemigo::Vulnerable),
+        _ => match sufijo.strip_prefix('E').map(str::parse::<i32>) {
+            Some(Ok(alcance)) if alcance > 0 => Ok(TipoDeEnemigo::Explosivo(alcance)),
+            _ => Err("Tipo de enemigo no válido"),
+        },
+    }
+}
+
+fn sufijo_de_enemigo(tipo: &TipoDeEnemigo) -> String {
+    match tipo {
+        TipoDeEnemigo::Comun => String::new(),
+        TipoDeEnemigo::Blindado => ":A".to_string(),
+        TipoDeEnemigo::Vulnerable => ":V".to_string(),
+        TipoDeEnemigo::Explosivo(alcance) => format!(":E{}", alcance),
+    }
+}
+
 fn convertir_bomba(simbolo: &str) -> Result<Objeto, &'static str> {
-    let alcance_str = &simbolo[1..];
+    let (alcance_str, direcciones) = separar_direcciones(&simbolo[1..])?;
     if let Ok(alcance) = alcance_str.parse::<i32>() {
         if alcance > 0 {
-            Ok(Objeto::Bomba(false, alcance))
+            Ok(Objeto::Bomba(false, alcance, direcciones))
         } else {
             Err("Valor de alcance de bomba no válido")
         }
@@ -65,10 +264,10 @@ fn convertir_bomba(simbolo: &str) -> Result<Objeto, &'static str> {
 }
 
 fn convertir_bomba_traspaso(simbolo: &str) -> Result<Objeto, &'static str> {
-    let alcance_str = &simbolo[1..];
+    let (alcance_str, direcciones) = separar_direcciones(&simbolo[1..])?;
     if let Ok(alcance) = alcance_str.parse::<i32>() {
         if alcance > 0 {
-            Ok(Objeto::Bomba(true, alcance))
+            Ok(Objeto::Bomba(true, alcance, direcciones))
         } else {
             Err("Valor de alcance de bomba de traspaso no válido")
         }
@@ -77,15 +276,136 @@ fn convertir_bomba_traspaso(simbolo: &str) -> Result<Objeto, &'static str> {
     }
 }
 
+/// Convierte una bomba de área: `A`, la forma (`C` para cuadrado, `R` para rombo) y el
+/// alcance, por ejemplo `AC2`. El sufijo `:T` indica que la explosión atraviesa las paredes.
+fn convertir_bomba_de_area(simbolo: &str) -> Result<Objeto, &'static str> {
+    let (resto, ocluida) = match simbolo.strip_suffix(":T") {
+        Some(resto) => (resto, false),
+        None => (simbolo, true),
+    };
+    let forma = match resto.get(1..2) {
+        Some("C") => Forma::Cuadrado,
+        Some("R") => Forma::Rombo,
+        _ => return Err("Forma de bomba de área no válida"),
+    };
+    match resto[2..].parse::<i32>() {
+        Ok(alcance) if alcance > 0 => Ok(Objeto::BombaDeArea(forma, alcance, ocluida)),
+        Ok(_) => Err("Valor de alcance de bomba de área no válido"),
+        Err(_) => Err("No se pudo parsear el valor de alcance de bomba de área"),
+    }
+}
+
+fn codigo_de_forma(forma: &Forma) -> &'static str {
+    match forma {
+        Forma::Cuadrado => "C",
+        Forma::Rombo => "R",
+    }
+}
+
+/// Separa el alcance de una bomba de su sufijo de direcciones: sin sufijo dispara en cruz,
+/// `:X` en diagonal, `:*` en las ocho direcciones, `:H` en horizontal y `:V` en vertical.
+/// También admite una lista de direcciones separadas por comas, como `:U,R` o `:DL`.
+fn separar_direcciones(texto: &str) -> Result<(&str, Direcciones), &'static str> {
+    let Some((alcance, sufijo)) = texto.split_once(':') else {
+        return Ok((texto, Direcciones::ORTOGONALES));
+    };
+    let direcciones = match sufijo {
+        "X" => Direcciones::DIAGONALES,
+        "*" => Direcciones::TODAS,
+        "H" => Direcciones::HORIZONTALES,
+        "V" => Direcciones::VERTICALES,
+        lista => {
+            let direcciones = lista
+                .split(',')
+                .map(direccion_de_codigo)
+                .collect::<Option<Vec<Direccion>>>()
+                .ok_or("Direcciones de bomba no válidas")?;
+            Direcciones::de(&direcciones)
+        }
+    };
+    Ok((alcance, direcciones))
+}
+
+fn sufijo_de_direcciones(direcciones: &Direcciones) -> String {
+    match *direcciones {
+        Direcciones::ORTOGONALES => String::new(),
+        Direcciones::DIAGONALES => ":X".to_string(),
+        Direcciones::TODAS => ":*".to_string(),
+        Direcciones::HORIZONTALES => ":H".to_string(),
+        Direcciones::VERTICALES => ":V".to_string(),
+        _ => {
+            let codigos: Vec<&str> = direcciones
+                .direcciones()
+                .iter()
+                .map(codigo_de_direccion)
+                .collect();
+            format!(":{}", codigos.join(","))
+        }
+    }
+}
+
+fn codigo_de_direccion(direccion: &Direccion) -> &'static str {
+    match direccion {
+        Direccion::Arriba => "U",
+        Direccion::Abajo => "D",
+        Direccion::Izquierda => "L",
+        Direccion::Derecha => "R",
+        Direccion::ArribaIzquierda => "UL",
+        Direccion::ArribaDerecha => "UR",
+        Direccion::AbajoIzquierda => "DL",
+        Direccion::AbajoDerecha => "DR",
+    }
+}
+
+fn direccion_de_codigo(codigo: &str) -> Option<Direccion> {
+    Direccion::TODAS
+        .into_iter()
+        .find(|direccion| codigo_de_direccion(direccion) == codigo)
+}
+
+fn convertir_portal(simbolo: &str) -> Result<Objeto, &'static str> {
+    simbolo[1..]
+        .parse::<u32>()
+        .map(Objeto::Portal)
+        .map_err(|_| "Identificador de portal no válido")
+}
+
+/// Convierte un desvío: `D` y el código de su dirección, seguidos opcionalmente de `:` y el
+/// identificador de los interruptores que lo hacen girar, por ejemplo `DR:2`.
 fn convertir_desvio(simbolo: &str) -> Result<Objeto, &'static str> {
-    let direccion = match &simbolo[1..] {
-        "U" => Direccion::Arriba,
-        "D" => Direccion::Abajo,
-        "L" => Direccion::Izquierda,
-        "R" => Direccion::Derecha,
-        _ => return Err("Dirección de desvío no válida"),
+    let (codigo, enlace) = match simbolo[1..].split_once(':') {
+        None => (&simbolo[1..], None),
+        Some((codigo, enlace)) => (
+            codigo,
+            Some(
+                enlace
+                    .parse::<u32>()
+                    .map_err(|_| "Identificador de enlace no válido")?,
+            ),
+        ),
+    };
+    direccion_de_codigo(codigo)
+        .map(|direccion| Objeto::Desvio(direccion, enlace))
+        .ok_or("Dirección de desvío no válida")
+}
+
+fn convertir_interruptor(simbolo: &str) -> Result<Objeto, &'static str> {
+    simbolo[1..]
+        .parse::<u32>()
+        .map(Objeto::Interruptor)
+        .map_err(|_| "Identificador de interruptor no válido")
+}
+
+/// Convierte una compuerta: `C` y el identificador de sus interruptores. Empieza cerrada,
+/// salvo que tenga el sufijo `:A`, por ejemplo `C2:A`.
+fn convertir_compuerta(simbolo: &str) -> Result<Objeto, &'static str> {
+    let (id, abierta) = match simbolo[1..].strip_suffix(":A") {
+        Some(id) => (id, true),
+        None => (&simbolo[1..], false),
     };
-    Ok(Objeto::Desvio(direccion))
+    id.parse::<u32>()
+        .map(|id| Objeto::Compuerta(id, abierta))
+        .map_err(|_| "Identificador de compuerta no válido")
 }
 
 #[cfg(test)]
@@ -98,23 +418,153 @@ mod tests {
         // Prueba para convertir un símbolo en un Objeto válido.
         assert_eq!(
             convertir_simbolos("F2"),
-            Ok(Objeto::Enemigo(2, HashSet::new()))
+            Ok(Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun))
         );
         assert_eq!(convertir_simbolos("R"), Ok(Objeto::Roca));
         assert_eq!(convertir_simbolos("W"), Ok(Objeto::Pared));
 
         // Prueba para convertir un símbolo en una Bomba y Bomba de Traspaso.
-        assert_eq!(convertir_simbolos("B2"), Ok(Objeto::Bomba(false, 2)));
-        assert_eq!(convertir_simbolos("S1"), Ok(Objeto::Bomba(true, 1)));
+        assert_eq!(
+            convertir_simbolos("B2"),
+            Ok(Objeto::Bomba(false, 2, Direcciones::ORTOGONALES))
+        );
+        assert_eq!(
+            convertir_simbolos("S1"),
+            Ok(Objeto::Bomba(true, 1, Direcciones::ORTOGONALES))
+        );
+
+        // Prueba para bombas diagonales, de ocho direcciones y con sufijo inválido.
+        assert_eq!(
+            convertir_simbolos("B2:X"),
+            Ok(Objeto::Bomba(false, 2, Direcciones::DIAGONALES))
+        );
+        assert_eq!(
+            convertir_simbolos("S1:*"),
+            Ok(Objeto::Bomba(true, 1, Direcciones::TODAS))
+        );
+        assert_eq!(
+            convertir_simbolos("B2:Q"),
+            Err("Direcciones de bomba no válidas")
+        );
+
+        // Prueba para bombas de línea y con direcciones elegidas una por una.
+        assert_eq!(
+            convertir_simbolos("B3:H"),
+            Ok(Objeto::Bomba(false, 3, Direcciones::HORIZONTALES))
+        );
+        assert_eq!(
+            convertir_simbolos("B3:L,R"),
+            Ok(Objeto::Bomba(false, 3, Direcciones::HORIZONTALES))
+        );
+        assert_eq!(
+            convertir_simbolos("S2:U"),
+            Ok(Objeto::Bomba(
+                true,
+                2,
+                Direcciones::de(&[Direccion::Arriba])
+            ))
+        );
+        assert_eq!(
+            convertir_simbolos("B2:U,"),
+            Err("Direcciones de bomba no válidas")
+        );
+        assert_eq!(
+            convertir_simbolos("B2:"),
+            Err("Direcciones de bomba no válidas")
+        );
+
+        // Prueba para tipos de enemigo.
+        assert_eq!(
+            convertir_simbolos("F2:A"),
+            Ok(Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Blindado))
+        );
+        assert_eq!(
+            convertir_simbolos("F1:E3"),
+            Ok(Objeto::Enemigo(
+                1,
+                HashSet::new(),
+                TipoDeEnemigo::Explosivo(3)
+            ))
+        );
+        assert_eq!(
+            convertir_simbolos("F1:E0"),
+            Err("Tipo de enemigo no válido")
+        );
+        assert_eq!(convertir_simbolos("F1:Z"), Err("Tipo de enemigo no válido"));
+        assert_eq!(
+            convertir_simbolos("F3:A"),
+            Err("Valor de vida de enemigo no válido")
+        );
+
+        // Prueba para bombas de área.
+        assert_eq!(
+            convertir_simbolos("AC2"),
+            Ok(Objeto::BombaDeArea(Forma::Cuadrado, 2, true))
+        );
+        assert_eq!(
+            convertir_simbolos("AR1:T"),
+            Ok(Objeto::BombaDeArea(Forma::Rombo, 1, false))
+        );
+        assert_eq!(
+            convertir_simbolos("AX2"),
+            Err("Forma de bomba de área no válida")
+        );
+        assert_eq!(
+            convertir_simbolos("AC0"),
+            Err("Valor de alcance de bomba de área no válido")
+        );
+        assert_eq!(
+            convertir_simbolos("AC"),
+            Err("No se pudo parsear el valor de alcance de bomba de área")
+        );
+
+        // Prueba para portales.
+        assert_eq!(convertir_simbolos("P3"), Ok(Objeto::Portal(3)));
+        assert_eq!(
+            convertir_simbolos("P"),
+            Err("Identificador de portal no válido")
+        );
+        assert_eq!(
+            convertir_simbolos("P-1"),
+            Err("Identificador de portal no válido")
+        );
+
+        // Prueba para interruptores, compuertas y desvíos enlazados.
+        assert_eq!(convertir_simbolos("I4"), Ok(Objeto::Interruptor(4)));
+        assert_eq!(convertir_simbolos("C4"), Ok(Objeto::Compuerta(4, false)));
+        assert_eq!(convertir_simbolos("C4:A"), Ok(Objeto::Compuerta(4, true)));
+        assert_eq!(
+            convertir_simbolos("DR:4"),
+            Ok(Objeto::Desvio(Direccion::Derecha, Some(4)))
+        );
+        assert_eq!(
+            convertir_simbolos("DR:"),
+            Err("Identificador de enlace no válido")
+        );
+        assert_eq!(
+            convertir_simbolos("I"),
+            Err("Identificador de interruptor no válido")
+        );
+        assert_eq!(
+            convertir_simbolos("C4:B"),
+            Err("Identificador de compuerta no válido")
+        );
+
+        // Prueba para espejos.
+        assert_eq!(convertir_simbolos("/"), Ok(Objeto::Espejo(Espejo::Barra)));
+        assert_eq!(
+            convertir_simbolos("\\"),
+            Ok(Objeto::Espejo(Espejo::BarraInvertida))
+        );
 
         // Prueba para convertir un símbolo en un Desvío.
         assert_eq!(
             convertir_simbolos("DU"),
-            Ok(Objeto::Desvio(Direccion::Arriba))
+            Ok(Objeto::Desvio(Direccion::Arriba, None))
         );
         assert_eq!(
             convertir_simbolos("DL"),
-            Ok(Objeto::Desvio(Direccion::Izquierda))
+            Ok(Objeto::Desvio(Direccion::Izquierda, None))
         );
 
         // Prueba para un símbolo no válido.
@@ -145,4 +595,109 @@ mod tests {
             Err("Valor de alcance de bomba de traspaso no válido")
         );
     }
+
+    #[test]
+    fn test_convertir_a_simbolo() {
+        let simbolos = [
+            "F2",
+            "B3",
+            "S1",
+            "B2:X",
+            "S4:*",
+            "B2:H",
+            "S3:V",
+            "B1:U",
+            "B2:D,R",
+            "B4:U,UL,DR",
+            "AC2",
+            "AR3:T",
+            "R",
+            "W",
+            "DU",
+            "DD",
+            "DL",
+            "DR",
+            "DUL",
+            "DUR",
+            "DDL",
+            "DDR",
+            "_",
+        ];
+        for simbolo in simbolos {
+            let objeto = convertir_simbolos(simbolo);
+            assert_eq!(
+                objeto.map(|o| convertir_a_simbolo(&o)),
+                Ok(simbolo.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_convertir_celda() {
+        assert_eq!(
+            convertir_celda("B2+DR"),
+            Ok((
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                Objeto::Desvio(Direccion::Derecha, None)
+            ))
+        );
+        assert_eq!(
+            convertir_celda("F1:A+P3"),
+            Ok((
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Blindado),
+                Objeto::Portal(3)
+            ))
+        );
+        assert_eq!(
+            convertir_celda("DR"),
+            convertir_simbolos("DR").map(|o| (o, Objeto::Vacio))
+        );
+        assert_eq!(
+            convertir_celda("B2+R"),
+            Err("Solo desvíos, espejos, divisores, portales e interruptores van en el suelo")
+        );
+        assert_eq!(
+            convertir_celda("W+DR"),
+            Err("Solo enemigos, bombas y rocas pueden estar sobre el suelo")
+        );
+        assert_eq!(convertir_celda("B2+"), Err("El símbolo está vacío"));
+
+        for simbolo in ["B2:X+/", "S1+Y", "AC1+I2", "R+DUL:1", "DR", "F2"] {
+            let celda = convertir_celda(simbolo).unwrap();
+            assert_eq!(convertir_celda_a_simbolo(&celda.0, &celda.1), simbolo);
+        }
+        assert_eq!(convertir_celda("_+DR"), convertir_celda("DR"));
+        assert_eq!(
+            convertir_celda_a_simbolo(&Objeto::Vacio, &Objeto::Divisor),
+            "Y"
+        );
+    }
+
+    #[test]
+    fn test_hash_no_depende_del_orden_de_las_afectadas() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let calcular = |objeto: &Objeto| {
+            let mut hasher = DefaultHasher::new();
+            objeto.hash(&mut hasher);
+            hasher.finish()
+        };
+        let mut primero = HashSet::new();
+        let mut segundo = HashSet::new();
+        for posicion in [(0, 0), (3, 1), (2, 2), (1, 4)] {
+            primero.insert(posicion);
+        }
+        for posicion in [(1, 4), (2, 2), (3, 1), (0, 0)] {
+            segundo.insert(posicion);
+        }
+        assert_eq!(
+            calcular(&Objeto::Enemigo(2, primero, TipoDeEnemigo::Comun)),
+            calcular(&Objeto::Enemigo(2, segundo, TipoDeEnemigo::Comun))
+        );
+        assert_ne!(
+            calcular(&Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun)),
+            calcular(&Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun))
+        );
+        assert_ne!(calcular(&Objeto::Roca), calcular(&Objeto::Pared));
+    }
 }
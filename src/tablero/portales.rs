@@ -0,0 +1,114 @@
+use super::model::objeto::Objeto;
+use super::Tablero;
+use std::collections::BTreeMap;
+
+impl Tablero {
+    /// Devuelve las posiciones `(x, y)` de los portales del tablero agrupadas por identificador,
+    /// recorriendo la cuadrícula por filas. Incluye los portales que están en el suelo.
+    pub fn portales(&self) -> BTreeMap<u32, Vec<(i32, i32)>> {
+        let mut portales: BTreeMap<u32, Vec<(i32, i32)>> = BTreeMap::new();
+        for ((x, y), objeto) in self.objetos_con_suelo() {
+            if let Objeto::Portal(id) = objeto {
+                portales.entry(*id).or_default().push((x as i32, y as i32));
+            }
+        }
+        portales
+    }
+
+    /// Revisa que cada portal tenga exactamente una pareja.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve un mensaje por cada identificador que no aparece exactamente dos veces, junto
+    /// con la posición del primer portal con ese identificador.
+    pub fn portales_sin_pareja(&self) -> Vec<((i32, i32), String)> {
+        self.portales()
+            .into_iter()
+            .filter(|(_, posiciones)| posiciones.len() != 2)
+            .map(|(id, posiciones)| {
+                let descripcion = if posiciones.len() == 1 {
+                    format!("El portal P{} no tiene pareja", id)
+                } else {
+                    format!(
+                        "El portal P{} aparece {} veces, pero debe formar una sola pareja",
+                        id,
+                        posiciones.len()
+                    )
+                };
+                (posiciones[0], descripcion)
+            })
+            .collect()
+    }
+
+    /// Devuelve la posición del portal por el que sale un rayo que entra al portal ubicado en
+    /// `(x, y)`, o `None` si ahí no hay un portal o si no tiene exactamente una pareja.
+    pub(super) fn salida_de_portal(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        let Some(Objeto::Portal(id)) = self.baldosa_en(x, y) else {
+            return None;
+        };
+        let posiciones = self.portales().remove(id)?;
+        if posiciones.len() != 2 {
+            return None;
+        }
+        posiciones
+            .into_iter()
+            .find(|&posicion| posicion != (x as i32, y as i32))
+            .map(|(salida_x, salida_y)| (salida_x as usize, salida_y as usize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tablero::leer_tablero;
+    use crate::tablero::model::enemigo::TipoDeEnemigo;
+    use crate::tablero::model::objeto::Objeto;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_salida_de_portal() {
+        let mut tablero = leer_tablero("P1 _ _\n_ _ _\n_ _ P1\n".as_bytes()).unwrap();
+        tablero.cuadricula[1][1] = Objeto::Portal(2);
+        assert_eq!(tablero.salida_de_portal(0, 0), Some((2, 2)));
+        assert_eq!(tablero.salida_de_portal(2, 2), Some((0, 0)));
+        assert_eq!(tablero.salida_de_portal(1, 1), None);
+        assert_eq!(tablero.salida_de_portal(1, 0), None);
+        assert_eq!(
+            tablero.portales_sin_pareja(),
+            vec![((1, 1), "El portal P2 no tiene pareja".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_rayo_atraviesa_portal() {
+        let mut tablero =
+            leer_tablero("B3 P1 F1 _\n_ _ _ _\n_ _ _ _\nP1 _ F1 _\n".as_bytes()).unwrap();
+        assert!(tablero.detonar(0, 0).is_ok());
+        // El rayo hacia la derecha entra en (1, 0) y sigue desde (0, 3) con alcance 2, así que
+        // el enemigo que está detrás del primer portal no recibe la explosión.
+        assert_eq!(
+            tablero.cuadricula[0][2],
+            Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun)
+        );
+        assert_eq!(tablero.cuadricula[3][2], Objeto::Vacio);
+    }
+
+    #[test]
+    fn test_ocupantes_sobre_portales() {
+        let mut tablero =
+            leer_tablero("B3:R F1+P1 _ _\n_ _ _ _\n_ _ _ _\nF1+P1 _ F1 _\n".as_bytes()).unwrap();
+        assert!(tablero.detonar(0, 0).is_ok());
+        // Reciben la explosión el enemigo parado sobre el portal de entrada, el parado sobre el
+        // de salida y el que está detrás de la salida.
+        assert_eq!(tablero.cantidad_enemigos(), 0);
+        assert_eq!(tablero.simbolo_en(0, 3), Some("P1".to_string()));
+
+        // Una roca sobre el portal de salida detiene el rayo.
+        let mut tablero =
+            leer_tablero("B3:R P1 _ _\n_ _ _ _\n_ _ _ _\nR+P1 _ F1 _\n".as_bytes()).unwrap();
+        assert!(tablero.detonar(0, 0).is_ok());
+        assert_eq!(
+            tablero.cuadricula[3][2],
+            Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun)
+        );
+    }
+}
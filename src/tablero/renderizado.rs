@@ -0,0 +1,242 @@
+use super::model::direccion::Direccion;
+use super::model::evento::Evento;
+use super::model::objeto::{convertir_a_simbolo, Objeto};
+use super::Tablero;
+use std::collections::HashSet;
+use std::io::{self, IsTerminal};
+
+const RESET: &str = "\x1b[0m";
+const FONDO_EXPLOSION: &str = "\x1b[43m";
+pub(super) const FONDO_CAMBIO: &str = "\x1b[41m";
+
+/// Indica si la salida estándar es una terminal y, por lo tanto, admite colores ANSI.
+pub fn salida_admite_color() -> bool {
+    io::stdout().is_terminal()
+}
+
+impl Tablero {
+    /// Dibuja el tablero para mostrarlo en una terminal, resaltando las celdas alcanzadas por
+    /// la última detonación.
+    ///
+    /// # Argumentos
+    ///
+    /// * `color`: Si es `true` usa colores ANSI y flechas Unicode para los desvíos; si es
+    ///   `false` usa solo ASCII y marca las celdas alcanzadas entre corchetes.
+    ///
+    pub fn renderizar(&self, color: bool) -> String {
+        self.dibujar(&self.celdas_de_ultima_explosion(), FONDO_EXPLOSION, color)
+    }
+
+    /// Dibuja el tablero resaltando las celdas de `resaltadas` con el color de fondo `fondo`,
+    /// o entre corchetes si no se usan colores.
+    pub(super) fn dibujar(
+        &self,
+        resaltadas: &HashSet<(i32, i32)>,
+        fondo: &str,
+        color: bool,
+    ) -> String {
+        let ancho = self
+            .cuadricula
+            .iter()
+            .enumerate()
+            .flat_map(|(y, fila)| (0..fila.len()).map(move |x| (x, y)))
+            .map(|(x, y)| self.texto_en(x, y, color).chars().count())
+            .max()
+            .unwrap_or(1);
+
+        let mut salida = String::new();
+        for (y, fila) in self.cuadricula.iter().enumerate() {
+            for (x, objeto) in fila.iter().enumerate() {
+                let fondo = if resaltadas.contains(&(x as i32, y as i32)) {
+                    Some(fondo)
+                } else {
+                    None
+                };
+                // Una celda sin ocupante toma el color de su suelo.
+                let coloreado = match (objeto, self.suelo_en(x, y)) {
+                    (Objeto::Vacio, suelo) if *suelo != Objeto::Vacio => suelo,
+                    _ => objeto,
+                };
+                let texto = self.texto_en(x, y, color);
+                salida.push_str(&dibujar_celda(&texto, coloreado, fondo, color, ancho));
+            }
+            salida.push('\n');
+        }
+        salida
+    }
+
+    /// Devuelve el texto de la celda `(x, y)`: el de su ocupante, seguido de `+` y el de su
+    /// suelo si tiene.
+    fn texto_en(&self, x: usize, y: usize, color: bool) -> String {
+        let ocupante = texto_de_celda(&self.cuadricula[y][x], color);
+        match self.suelo_en(x, y) {
+            Objeto::Vacio => ocupante,
+            suelo => format!("{}+{}", ocupante, texto_de_celda(suelo, color)),
+        }
+    }
+
+    fn celdas_de_ultima_explosion(&self) -> HashSet<(i32, i32)> {
+        self.eventos
+            .iter()
+            .map(|evento| match evento {
+                Evento::Detonacion(x, y) => (*x, *y),
+                Evento::Alcance(x, y, _) => (*x, *y),
+                Evento::Impacto(x, y, _, _) => (*x, *y),
+                Evento::Conmutacion(x, y, _) => (*x, *y),
+            })
+            .collect()
+    }
+}
+
+fn dibujar_celda(
+    texto: &str,
+    objeto: &Objeto,
+    fondo: Option<&str>,
+    color: bool,
+    ancho: usize,
+) -> String {
+    let texto = format!("{:^ancho$}", texto);
+    if !color {
+        return if fondo.is_some() {
+            format!("[{}]", texto)
+        } else {
+            format!(" {} ", texto)
+        };
+    }
+
+    format!(
+        "{}{} {} {}",
+        fondo.unwrap_or(""),
+        color_de_objeto(objeto),
+        texto,
+        RESET
+    )
+}
+
+fn texto_de_celda(objeto: &Objeto, color: bool) -> String {
+    match objeto {
+        Objeto::Desvio(direccion, None) => flecha(direccion, color).to_string(),
+        Objeto::Desvio(direccion, Some(enlace)) => {
+            format!("{}{}", flecha(direccion, color), enlace)
+        }
+        Objeto::Vacio if color => "·".to_string(),
+        _ => convertir_a_simbolo(objeto),
+    }
+}
+
+fn flecha(direccion: &Direccion, unicode: bool) -> &'static str {
+    match (direccion, unicode) {
+        (Direccion::Arriba, true) => "↑",
+        (Direccion::Abajo, true) => "↓",
+        (Direccion::Izquierda, true) => "←",
+        (Direccion::Derecha, true) => "→",
+        (Direccion::Arriba, false) => "^",
+        (Direccion::Abajo, false) => "v",
+        (Direccion::Izquierda, false) => "<",
+        (Direccion::Derecha, false) => ">",
+        (Direccion::ArribaIzquierda, true) => "↖",
+        (Direccion::ArribaDerecha, true) => "↗",
+        (Direccion::AbajoIzquierda, true) => "↙",
+        (Direccion::AbajoDerecha, true) => "↘",
+        (Direccion::ArribaIzquierda, false) => "^<",
+        (Direccion::ArribaDerecha, false) => "^>",
+        (Direccion::AbajoIzquierda, false) => "v<",
+        (Direccion::AbajoDerecha, false) => "v>",
+    }
+}
+
+fn color_de_objeto(objeto: &Objeto) -> &'static str {
+    match objeto {
+        Objeto::Enemigo(_, _, _) => "\x1b[1;32m",
+        Objeto::Bomba(false, _, _) => "\x1b[1;31m",
+        Objeto::Bomba(true, _, _) => "\x1b[1;35m",
+        Objeto::BombaDeArea(_, _, _) => "\x1b[1;91m",
+        Objeto::Roca => "\x1b[33m",
+        Objeto::Pared => "\x1b[1;37;100m",
+        Objeto::Desvio(_, _) | Objeto::Espejo(_) | Objeto::Divisor => "\x1b[1;36m",
+        Objeto::Interruptor(_) => "\x1b[1;93m",
+        Objeto::Compuerta(_, false) => "\x1b[1;93;100m",
+        Objeto::Compuerta(_, true) => "\x1b[93m",
+        Objeto::Portal(_) => "\x1b[1;34m",
+        Objeto::Vacio => "\x1b[90m",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tablero::model::direccion::Direcciones;
+    use crate::tablero::model::enemigo::TipoDeEnemigo;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_renderizar_ascii() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+                Objeto::Desvio(Direccion::Izquierda, None),
+            ],
+            vec![
+                Objeto::Pared,
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+                Objeto::Roca,
+            ],
+            vec![
+                Objeto::Vacio,
+                Objeto::Vacio,
+                Objeto::Bomba(true, 2, Direcciones::ORTOGONALES),
+            ],
+        ];
+        assert_eq!(
+            tablero.renderizar(false),
+            " B1  _   <  \n W   F2  R  \n _   _   S2 \n"
+        );
+    }
+
+    #[test]
+    fn test_renderizar_ascii_resalta_ultima_explosion() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+                Objeto::Desvio(Direccion::Izquierda, None),
+            ],
+            vec![
+                Objeto::Pared,
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+                Objeto::Roca,
+            ],
+            vec![
+                Objeto::Vacio,
+                Objeto::Vacio,
+                Objeto::Bomba(true, 2, Direcciones::ORTOGONALES),
+            ],
+        ];
+        assert!(tablero.detonar(0, 0).is_ok());
+        assert_eq!(
+            tablero.renderizar(false),
+            "[_ ][_ ] <  \n W   F2  R  \n _   _   S2 \n"
+        );
+    }
+
+    #[test]
+    fn test_renderizar_color() {
+        let mut tablero = Tablero::new(2);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Desvio(Direccion::Arriba, None),
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+            ],
+            vec![Objeto::Vacio, Objeto::Vacio],
+        ];
+        assert!(tablero.detonar(1, 0).is_ok());
+
+        let dibujo = tablero.renderizar(true);
+        assert!(dibujo.contains("↑"));
+        assert!(dibujo.contains(&format!("{}\x1b[90m ·", FONDO_EXPLOSION)));
+        assert!(dibujo.ends_with(&format!("{}\n", RESET)));
+    }
+}
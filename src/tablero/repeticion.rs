@@ -0,0 +1,433 @@
+//! Grabación y reproducción de sesiones de detonación.
+//!
+//! Una repetición es un archivo de texto con una instrucción por línea:
+//!
+//! ```text
+//! # Las líneas que empiezan con '#' y las vacías se ignoran.
+//! cargar maze.txt
+//! detonar 0 0
+//! editar 2 1 R
+//! checksum 9f3a5c0e12b4d678
+//! ```
+//!
+//! `cargar` debe ser la primera instrucción; la ruta es relativa al directorio de la
+//! repetición. `checksum` es opcional y debe ser la última: indica el checksum que debe tener
+//! el tablero final. El modelo no tiene jugador ni turnos, así que no hay instrucciones para
+//! moverse ni para avanzar el tiempo.
+
+use super::model::objeto::{convertir_a_simbolo, convertir_simbolos, Objeto};
+use super::{crear_tablero, Tablero, DIRECTIVA_TOROIDAL};
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+const FNV_BASE: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIMO: u64 = 0x0000_0100_0000_01b3;
+
+/// Representa una instrucción de una repetición.
+#[derive(PartialEq, Debug, Clone)]
+pub enum Instruccion {
+    /// Carga el tablero desde un archivo.
+    Cargar(String),
+    /// Detona la bomba en `(x, y)`.
+    Detonar(i32, i32),
+    /// Reemplaza el objeto en `(x, y)`.
+    Editar(i32, i32, Objeto),
+}
+
+impl fmt::Display for Instruccion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruccion::Cargar(archivo) => write!(f, "cargar {}", archivo),
+            Instruccion::Detonar(x, y) => write!(f, "detonar {} {}", x, y),
+            Instruccion::Editar(x, y, objeto) => {
+                write!(f, "editar {} {} {}", x, y, convertir_a_simbolo(objeto))
+            }
+        }
+    }
+}
+
+/// Representa los errores al reproducir una repetición, separados según su causa para que
+/// quien la reproduce pueda informarlos de forma distinta.
+#[derive(PartialEq, Debug, Clone)]
+pub enum ErrorDeRepeticion {
+    /// No se pudo cargar el tablero de `cargar`; guarda la clase del error de E/S, que es
+    /// `io::ErrorKind::InvalidData` si el archivo no es un tablero válido.
+    Carga(io::ErrorKind, String),
+    /// Una instrucción `detonar` no encontró una bomba.
+    Detonacion(String),
+    /// Una instrucción no se puede aplicar al tablero, por ejemplo una edición fuera de él.
+    Instruccion(String),
+}
+
+impl fmt::Display for ErrorDeRepeticion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorDeRepeticion::Carga(_, mensaje)
+            | ErrorDeRepeticion::Detonacion(mensaje)
+            | ErrorDeRepeticion::Instruccion(mensaje) => write!(f, "{}", mensaje),
+        }
+    }
+}
+
+/// Representa una sesión grabada: las instrucciones en orden y, opcionalmente, el checksum
+/// del tablero final.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Repeticion {
+    pub instrucciones: Vec<Instruccion>,
+    pub checksum: Option<u64>,
+}
+
+impl Repeticion {
+    /// Lee una repetición desde su formato de texto.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve la repetición, o `Err(String)` indicando la línea con problemas.
+    pub fn parsear(contenido: &str) -> Result<Repeticion, String> {
+        let mut repeticion = Repeticion {
+            instrucciones: Vec::new(),
+            checksum: None,
+        };
+        for (indice, linea) in contenido.lines().enumerate() {
+            let linea = linea.trim();
+            if linea.is_empty() || linea.starts_with('#') {
+                continue;
+            }
+            if repeticion.checksum.is_some() {
+                return Err(format!(
+                    "ERROR en la línea {}: 'checksum' debe ser la última instrucción.",
+                    indice + 1
+                ));
+            }
+            let palabras: Vec<&str> = linea.split_whitespace().collect();
+            let error = |detalle: &str| format!("ERROR en la línea {}: {}", indice + 1, detalle);
+
+            match palabras.as_slice() {
+                ["cargar", archivo] => {
+                    if !repeticion.instrucciones.is_empty() {
+                        return Err(error("solo se puede cargar un tablero al comienzo."));
+                    }
+                    repeticion
+                        .instrucciones
+                        .push(Instruccion::Cargar(archivo.to_string()));
+                }
+                _ if repeticion.instrucciones.is_empty() => {
+                    return Err(error("la primera instrucción debe ser 'cargar'."));
+                }
+                ["detonar", x, y] => {
+                    let (x, y) = parse_posicion(x, y).ok_or_else(|| error("posición inválida."))?;
+                    repeticion.instrucciones.push(Instruccion::Detonar(x, y));
+                }
+                ["editar", x, y, simbolo] => {
+                    let (x, y) = parse_posicion(x, y).ok_or_else(|| error("posición inválida."))?;
+                    let objeto = convertir_simbolos(simbolo).map_err(error)?;
+                    repeticion
+                        .instrucciones
+                        .push(Instruccion::Editar(x, y, objeto));
+                }
+                ["checksum", valor] => {
+                    let checksum = u64::from_str_radix(valor, 16)
+                        .map_err(|_| error("el checksum debe ser un número hexadecimal."))?;
+                    repeticion.checksum = Some(checksum);
+                }
+                _ => return Err(error(&format!("instrucción desconocida: {}", linea))),
+            }
+        }
+        if repeticion.instrucciones.is_empty() {
+            return Err("ERROR: La repetición está vacía.".to_string());
+        }
+        Ok(repeticion)
+    }
+
+    /// Devuelve la repetición en su formato de texto, una instrucción por línea.
+    pub fn a_texto(&self) -> String {
+        let mut texto: String = self
+            .instrucciones
+            .iter()
+            .map(|instruccion| format!("{}\n", instruccion))
+            .collect();
+        if let Some(checksum) = self.checksum {
+            texto.push_str(&format!("checksum {:016x}\n", checksum));
+        }
+        texto
+    }
+
+    /// Ejecuta las instrucciones de la repetición y devuelve el tablero final.
+    ///
+    /// # Argumentos
+    ///
+    /// * `directorio`: El directorio desde el que se resuelve la ruta de `cargar`.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve el tablero final, o un `ErrorDeRepeticion` que indica si falló la carga del
+    /// tablero, una detonación u otra instrucción.
+    pub fn reproducir(&self, directorio: &Path) -> Result<Tablero, ErrorDeRepeticion> {
+        let sin_tablero =
+            || ErrorDeRepeticion::Instruccion("ERROR: No se cargó ningún tablero.".to_string());
+        let mut tablero: Option<Tablero> = None;
+        for instruccion in &self.instrucciones {
+            match (instruccion, tablero.as_mut()) {
+                (Instruccion::Cargar(archivo), _) => {
+                    let ruta = directorio.join(archivo);
+                    let cargado = crear_tablero(&ruta.to_string_lossy()).map_err(|e| {
+                        ErrorDeRepeticion::Carga(
+                            e.kind(),
+                            format!("ERROR: No se pudo cargar {}: {}", archivo, e),
+                        )
+                    })?;
+                    tablero = Some(cargado);
+                }
+                (Instruccion::Detonar(x, y), Some(tablero)) => tablero
+                    .detonar(*x, *y)
+                    .map_err(ErrorDeRepeticion::Detonacion)?,
+                (Instruccion::Editar(x, y, objeto), Some(tablero)) => {
+                    editar(tablero, *x, *y, objeto.clone())
+                        .map_err(ErrorDeRepeticion::Instruccion)?
+                }
+                (_, None) => return Err(sin_tablero()),
+            }
+        }
+        tablero.ok_or_else(sin_tablero)
+    }
+
+    /// Verifica que `tablero` tenga el checksum guardado en la repetición.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve `Ok(())` si coincide o si la repetición no tiene checksum.
+    pub fn verificar(&self, tablero: &Tablero) -> Result<(), String> {
+        match self.checksum {
+            Some(esperado) if esperado != tablero.checksum() => Err(format!(
+                "ERROR: El tablero final no coincide: checksum esperado {:016x}, obtenido {:016x}.",
+                esperado,
+                tablero.checksum()
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Graba una sesión mientras se modifica el tablero, para poder reproducirla después.
+#[derive(Debug, Clone)]
+pub struct Grabador {
+    tablero: Tablero,
+    instrucciones: Vec<Instruccion>,
+}
+
+impl Grabador {
+    /// Carga el tablero de `archivo` y comienza la grabación.
+    ///
+    /// # Argumentos
+    ///
+    /// * `archivo`: La ruta del tablero, tal como se guardará en la repetición.
+    /// * `directorio`: El directorio desde el que se resuelve `archivo`.
+    ///
+    pub fn cargar(archivo: &str, directorio: &Path) -> Result<Grabador, io::Error> {
+        let tablero = crear_tablero(&directorio.join(archivo).to_string_lossy())?;
+        Ok(Grabador {
+            tablero,
+            instrucciones: vec![Instruccion::Cargar(archivo.to_string())],
+        })
+    }
+
+    /// Devuelve el tablero en su estado actual.
+    pub fn tablero(&self) -> &Tablero {
+        &self.tablero
+    }
+
+    /// Detona la bomba en `(x, y)` y lo graba. Si la detonación falla no se graba nada.
+    pub fn detonar(&mut self, x: i32, y: i32) -> Result<(), String> {
+        self.tablero.detonar(x, y)?;
+        self.instrucciones.push(Instruccion::Detonar(x, y));
+        Ok(())
+    }
+
+    /// Reemplaza el objeto en `(x, y)` y lo graba. Si la posición no existe no se graba nada.
+    pub fn editar(&mut self, x: i32, y: i32, objeto: Objeto) -> Result<(), String> {
+        editar(&mut self.tablero, x, y, objeto.clone())?;
+        self.instrucciones.push(Instruccion::Editar(x, y, objeto));
+        Ok(())
+    }
+
+    /// Termina la grabación y devuelve la repetición con el checksum del tablero final.
+    pub fn finalizar(self) -> Repeticion {
+        Repeticion {
+            checksum: Some(self.tablero.checksum()),
+            instrucciones: self.instrucciones,
+        }
+    }
+}
+
+impl Tablero {
+    /// Calcula un checksum FNV-1a de 64 bits del tablero. Tiene en cuenta si es toroidal, el
+    /// símbolo de cada celda y las bombas que ya golpearon a cada enemigo, de modo que dos
+    /// tableros con el mismo checksum se comportan igual al detonar.
+    pub fn checksum(&self) -> u64 {
+        let mut hash = FNV_BASE;
+        let mut agregar = |bytes: &[u8]| {
+            for byte in bytes {
+                hash ^= u64::from(*byte);
+                hash = hash.wrapping_mul(FNV_PRIMO);
+            }
+        };
+        if self.toroidal {
+            agregar(DIRECTIVA_TOROIDAL.as_bytes());
+            agregar(b"\n");
+        }
+        for (y, fila) in self.cuadricula.iter().enumerate() {
+            for (x, objeto) in fila.iter().enumerate() {
+                agregar(self.simbolo_en(x, y).unwrap_or_default().as_bytes());
+                if let Objeto::Enemigo(_, afectadas, _) = objeto {
+                    let mut afectadas: Vec<&(i32, i32)> = afectadas.iter().collect();
+                    afectadas.sort();
+                    for (x, y) in afectadas {
+                        agregar(format!("@{},{}", x, y).as_bytes());
+                    }
+                }
+                agregar(b" ");
+            }
+            agregar(b"\n");
+        }
+        hash
+    }
+}
+
+fn parse_posicion(x: &str, y: &str) -> Option<(i32, i32)> {
+    Some((x.parse().ok()?, y.parse().ok()?))
+}
+
+fn editar(tablero: &mut Tablero, x: i32, y: i32, objeto: Objeto) -> Result<(), String> {
+    let celda = usize::try_from(y)
+        .ok()
+        .zip(usize::try_from(x).ok())
+        .and_then(|(fila, columna)| tablero.cuadricula.get_mut(fila)?.get_mut(columna))
+        .ok_or_else(|| format!("ERROR: La posición ({}, {}) está fuera del tablero.", x, y))?;
+    *celda = objeto;
+    tablero.eventos.clear();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tablero::model::enemigo::TipoDeEnemigo;
+    use std::collections::HashSet;
+    use std::fs;
+
+    const REPETICION: &str = "\
+# Sesión de prueba
+cargar tablero.txt
+
+detonar 0 0
+editar 2 2 R
+";
+
+    fn directorio_de_prueba(nombre: &str) -> std::path::PathBuf {
+        let directorio = std::env::temp_dir().join(format!("tp_repeticion_{}", nombre));
+        let creado = fs::create_dir_all(&directorio)
+            .and_then(|_| fs::write(directorio.join("tablero.txt"), "B2 F2 _\nW R _\n_ _ _\n"));
+        assert!(creado.is_ok());
+        directorio
+    }
+
+    #[test]
+    fn test_parsear_y_a_texto() {
+        let repeticion = Repeticion::parsear(REPETICION);
+        assert_eq!(
+            repeticion,
+            Ok(Repeticion {
+                instrucciones: vec![
+                    Instruccion::Cargar("tablero.txt".to_string()),
+                    Instruccion::Detonar(0, 0),
+                    Instruccion::Editar(2, 2, Objeto::Roca),
+                ],
+                checksum: None,
+            })
+        );
+        assert_eq!(
+            repeticion.map(|r| r.a_texto()),
+            Ok("cargar tablero.txt\ndetonar 0 0\neditar 2 2 R\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parsear_errores() {
+        assert!(Repeticion::parsear("").is_err());
+        assert!(Repeticion::parsear("detonar 0 0\n").is_err());
+        assert_eq!(
+            Repeticion::parsear("cargar a.txt\nmover arriba\n"),
+            Err("ERROR en la línea 2: instrucción desconocida: mover arriba".to_string())
+        );
+        assert!(Repeticion::parsear("cargar a.txt\ndetonar x 0\n").is_err());
+        assert!(Repeticion::parsear("cargar a.txt\neditar 0 0 Q\n").is_err());
+        assert!(Repeticion::parsear("cargar a.txt\nchecksum 1\ndetonar 0 0\n").is_err());
+    }
+
+    #[test]
+    fn test_grabar_y_reproducir() {
+        let directorio = directorio_de_prueba("grabar");
+        let grabador = Grabador::cargar("tablero.txt", &directorio);
+        assert!(grabador.is_ok());
+        let Ok(mut grabador) = grabador else { return };
+        assert!(grabador.detonar(0, 0).is_ok());
+        assert!(grabador.detonar(1, 1).is_err());
+        assert!(grabador.editar(2, 2, Objeto::Roca).is_ok());
+        let final_grabado = grabador.tablero().clone();
+
+        let texto = grabador.finalizar().a_texto();
+        let repeticion = Repeticion::parsear(&texto);
+        assert!(repeticion.is_ok());
+        let Ok(repeticion) = repeticion else { return };
+        assert_eq!(
+            repeticion.instrucciones,
+            Repeticion::parsear(REPETICION)
+                .map(|r| r.instrucciones)
+                .unwrap_or_default()
+        );
+
+        let reproducido = repeticion.reproducir(&directorio);
+        assert!(reproducido.is_ok());
+        let Ok(reproducido) = reproducido else { return };
+        assert_eq!(reproducido.cuadricula, final_grabado.cuadricula);
+        assert!(repeticion.verificar(&reproducido).is_ok());
+    }
+
+    #[test]
+    fn test_verificar_checksum_distinto() {
+        let directorio = directorio_de_prueba("verificar");
+        let repeticion = Repeticion::parsear("cargar tablero.txt\nchecksum 0\n");
+        let Ok(repeticion) = repeticion else {
+            panic!("No se pudo parsear la repetición");
+        };
+        let tablero = repeticion.reproducir(&directorio);
+        assert!(tablero.is_ok_and(|t| repeticion.verificar(&t).is_err()));
+    }
+
+    #[test]
+    fn test_checksum_distingue_bombas_que_golpearon() {
+        let mut tablero = Tablero::new(1);
+        tablero.cuadricula = vec![vec![Objeto::Enemigo(
+            1,
+            HashSet::new(),
+            TipoDeEnemigo::Comun,
+        )]];
+        let antes = tablero.checksum();
+        assert_eq!(antes, tablero.clone().checksum());
+
+        tablero.cuadricula[0][0] =
+            Objeto::Enemigo(1, [(3, 4)].into_iter().collect(), TipoDeEnemigo::Comun);
+        assert_ne!(antes, tablero.checksum());
+    }
+
+    #[test]
+    fn test_checksum_distingue_tableros_toroidales() {
+        let mut tablero = Tablero::new(1);
+        tablero.cuadricula = vec![vec![Objeto::Vacio]];
+        let plano = tablero.checksum();
+
+        tablero.toroidal = true;
+        assert_ne!(plano, tablero.checksum());
+    }
+}
@@ -0,0 +1,432 @@
+use super::model::evento::Evento;
+use super::Tablero;
+use std::collections::{BTreeSet, HashSet};
+
+/// Cantidad máxima de detonaciones simuladas que hace la búsqueda de la menor cantidad de
+/// detonaciones manuales antes de darse por vencida.
+pub const LIMITE_DE_SIMULACIONES: usize = 20_000;
+
+/// Resultado de buscar cómo eliminar a todos los enemigos del tablero.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Resolucion {
+    /// Se eliminan todos los enemigos con esta cantidad mínima de detonaciones manuales.
+    Resoluble(usize),
+    /// Ninguna combinación de detonaciones elimina a todos los enemigos.
+    Irresoluble,
+    /// La búsqueda llegó al límite de simulaciones sin encontrar la cantidad mínima.
+    Desconocida,
+}
+
+impl Tablero {
+    /// Busca la menor cantidad de detonaciones manuales que eliminan a todos los enemigos,
+    /// teniendo en cuenta las reacciones en cadena, con a lo sumo `LIMITE_DE_SIMULACIONES`
+    /// detonaciones simuladas.
+    pub fn resolucion(&self) -> Resolucion {
+        self.resolucion_con_limite(LIMITE_DE_SIMULACIONES)
+    }
+
+    /// Igual que `resolucion`, pero con a lo sumo `limite` detonaciones simuladas.
+    ///
+    /// Sin interruptores, como cada bomba explota a lo sumo una vez y un enemigo solo recibe un
+    /// golpe por bomba, detonar todas las bombas es la mejor jugada posible: si así no se
+    /// eliminan todos los enemigos, ninguna cadena de detonaciones lo logra. Por la misma razón,
+    /// una bomba cuya cadena está contenida en la de otra nunca hace falta detonarla a mano, así
+    /// que solo se prueban combinaciones de las demás.
+    ///
+    /// Con interruptores eso deja de valer: una detonación puede cerrar una compuerta o girar
+    /// un desvío y tapar el camino de la siguiente. En ese caso se prueban secuencias de
+    /// detonaciones en orden, de las más cortas a las más largas.
+    pub fn resolucion_con_limite(&self, limite: usize) -> Resolucion {
+        if self.cantidad_enemigos() == 0 {
+            return Resolucion::Resoluble(0);
+        }
+        if !self.interruptores().is_empty() {
+            return self.resolucion_por_secuencias(limite);
+        }
+        let bombas = self.posiciones_bombas();
+        if !self.queda_limpio(&bombas) {
+            return Resolucion::Irresoluble;
+        }
+
+        let candidatas = self.bombas_no_dominadas(&bombas);
+        let mut simulaciones = 0;
+        for cantidad in 1..candidatas.len() {
+            let mut indices: Vec<usize> = (0..cantidad).collect();
+            loop {
+                if simulaciones == limite {
+                    return Resolucion::Desconocida;
+                }
+                simulaciones += 1;
+                let semillas: Vec<(i32, i32)> = indices.iter().map(|&i| candidatas[i]).collect();
+                if self.queda_limpio(&semillas) {
+                    return Resolucion::Resoluble(cantidad);
+                }
+                if !siguiente_combinacion(&mut indices, candidatas.len()) {
+                    break;
+                }
+            }
+        }
+        // Las cadenas de las candidatas cubren todas las bombas, así que detonarlas todas
+        // equivale a detonar todas las bombas.
+        Resolucion::Resoluble(candidatas.len())
+    }
+
+    /// Indica si se sabe que el tablero puede quedar sin enemigos. Devuelve `false` tanto si
+    /// no es resoluble como si la búsqueda no llegó a decidirlo.
+    pub fn es_resoluble(&self) -> bool {
+        matches!(self.resolucion(), Resolucion::Resoluble(_))
+    }
+
+    /// Calcula la menor cantidad de detonaciones manuales necesarias para eliminar a todos
+    /// los enemigos, teniendo en cuenta las reacciones en cadena.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve `Some(cantidad)` si el tablero es resoluble, o `None` si no lo es o si la
+    /// búsqueda no llegó a decidirlo.
+    pub fn detonaciones_minimas(&self) -> Option<usize> {
+        match self.resolucion() {
+            Resolucion::Resoluble(cantidad) => Some(cantidad),
+            Resolucion::Irresoluble | Resolucion::Desconocida => None,
+        }
+    }
+
+    /// Indica si el tablero es resoluble y requiere exactamente `detonaciones` manuales.
+    ///
+    /// # Argumentos
+    ///
+    /// * `detonaciones`: La cantidad de detonaciones manuales que debe exigir el tablero.
+    ///
+    pub fn requiere_detonaciones(&self, detonaciones: usize) -> bool {
+        self.detonaciones_minimas() == Some(detonaciones)
+    }
+
+    /// Busca la secuencia más corta de detonaciones manuales que deja el tablero sin enemigos,
+    /// probando cada bomba que queda en cada tablero alcanzado. Los tableros repetidos se
+    /// descartan por su checksum.
+    fn resolucion_por_secuencias(&self, limite: usize) -> Resolucion {
+        let mut frontera = vec![self.clone()];
+        let mut vistos = HashSet::from([self.checksum()]);
+        let mut simulaciones = 0;
+        let mut cantidad = 0;
+        while !frontera.is_empty() {
+            cantidad += 1;
+            let mut siguientes = Vec::new();
+            for estado in &frontera {
+                for (x, y) in estado.posiciones_bombas() {
+                    if simulaciones == limite {
+                        return Resolucion::Desconocida;
+                    }
+                    simulaciones += 1;
+                    let mut simulacion = estado.clone();
+                    if simulacion.detonar(x, y).is_err() {
+                        continue;
+                    }
+                    if simulacion.cantidad_enemigos() == 0 {
+                        return Resolucion::Resoluble(cantidad);
+                    }
+                    if vistos.insert(simulacion.checksum()) {
+                        siguientes.push(simulacion);
+                    }
+                }
+            }
+            frontera = siguientes;
+        }
+        Resolucion::Irresoluble
+    }
+
+    /// Devuelve las bombas cuya cadena no está contenida en la de otra bomba. Si varias bombas
+    /// tienen la misma cadena, se queda con la primera en el orden de la cuadrícula.
+    fn bombas_no_dominadas(&self, bombas: &[(i32, i32)]) -> Vec<(i32, i32)> {
+        let mut cadenas: Vec<_> = bombas
+            .iter()
+            .map(|&bomba| (bomba, self.bombas_detonadas(&[bomba])))
+            .collect();
+        cadenas.sort_by_key(|(_, cadena)| std::cmp::Reverse(cadena.len()));
+
+        let mut candidatas = Vec::new();
+        let mut cubiertas: Vec<BTreeSet<(i32, i32)>> = Vec::new();
+        for (bomba, cadena) in cadenas {
+            if !cubiertas.iter().any(|mayor| mayor.is_superset(&cadena)) {
+                candidatas.push(bomba);
+                cubiertas.push(cadena);
+            }
+        }
+        candidatas.sort_by_key(|&(x, y)| (y, x));
+        candidatas
+    }
+
+    /// Devuelve las posiciones de todas las bombas que explotan al detonar `semillas` en orden.
+    fn bombas_detonadas(&self, semillas: &[(i32, i32)]) -> BTreeSet<(i32, i32)> {
+        let mut simulacion = self.clone();
+        let mut detonadas = BTreeSet::new();
+        for &(x, y) in semillas {
+            if simulacion.detonar(x, y).is_ok() {
+                detonadas.extend(simulacion.eventos.iter().filter_map(|evento| match evento {
+                    Evento::Detonacion(x, y) => Some((*x, *y)),
+                    _ => None,
+                }));
+            }
+        }
+        detonadas
+    }
+
+    fn queda_limpio(&self, semillas: &[(i32, i32)]) -> bool {
+        let mut simulacion = self.clone();
+        for &(x, y) in semillas {
+            // Si la bomba ya explotó en cadena, la detonación manual no hace falta.
+            let _ya_detonada = simulacion.detonar(x, y);
+        }
+        simulacion.cantidad_enemigos() == 0
+    }
+}
+
+fn siguiente_combinacion(indices: &mut [usize], total: usize) -> bool {
+    let k = indices.len();
+    for i in (0..k).rev() {
+        if indices[i] < total - k + i {
+            indices[i] += 1;
+            for j in i + 1..k {
+                indices[j] = indices[j - 1] + 1;
+            }
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tablero::model::direccion::{Direccion, Direcciones};
+    use crate::tablero::model::enemigo::TipoDeEnemigo;
+    use crate::tablero::model::objeto::Objeto;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_tablero_sin_enemigos_es_resoluble() {
+        let mut tablero = Tablero::new(2);
+        tablero.cuadricula = vec![
+            vec![Objeto::Vacio, Objeto::Roca],
+            vec![Objeto::Pared, Objeto::Vacio],
+        ];
+
+        assert!(tablero.es_resoluble());
+        assert_eq!(tablero.detonaciones_minimas(), Some(0));
+    }
+
+    #[test]
+    fn test_enemigo_protegido_por_pared() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                Objeto::Pared,
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![Objeto::Vacio, Objeto::Vacio, Objeto::Vacio],
+            vec![Objeto::Vacio, Objeto::Vacio, Objeto::Vacio],
+        ];
+
+        assert!(!tablero.es_resoluble());
+        assert_eq!(tablero.detonaciones_minimas(), None);
+    }
+
+    #[test]
+    fn test_enemigo_alcanzado_por_desvio() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 3, Direcciones::ORTOGONALES),
+                Objeto::Desvio(Direccion::Abajo, None),
+                Objeto::Pared,
+            ],
+            vec![
+                Objeto::Pared,
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+                Objeto::Vacio,
+            ],
+            vec![Objeto::Vacio, Objeto::Vacio, Objeto::Vacio],
+        ];
+
+        assert!(tablero.es_resoluble());
+        assert_eq!(tablero.detonaciones_minimas(), Some(1));
+        assert!(tablero.requiere_detonaciones(1));
+        assert!(!tablero.requiere_detonaciones(2));
+    }
+
+    #[test]
+    fn test_cadena_reduce_detonaciones_manuales() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(2, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![Objeto::Pared, Objeto::Pared, Objeto::Pared],
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Pared,
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+        ];
+
+        // El enemigo de la fila inferior no lo alcanza ninguna bomba.
+        assert!(!tablero.es_resoluble());
+
+        tablero.cuadricula[2][2] = Objeto::Vacio;
+        // Una sola detonación en (0, 0) encadena la bomba de (1, 0), pero el enemigo de vida 2
+        // solo recibe el golpe de esta última.
+        assert!(!tablero.es_resoluble());
+
+        tablero.cuadricula[0][2] = Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun);
+        assert_eq!(tablero.detonaciones_minimas(), Some(1));
+        assert!(tablero.requiere_detonaciones(1));
+    }
+
+    #[test]
+    fn test_detonaciones_minimas_sin_cadena() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+                Objeto::Vacio,
+            ],
+            vec![Objeto::Pared, Objeto::Pared, Objeto::Pared],
+            vec![
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+            ],
+        ];
+
+        assert_eq!(tablero.detonaciones_minimas(), Some(2));
+        assert!(!tablero.requiere_detonaciones(1));
+    }
+
+    #[test]
+    fn test_no_modifica_el_tablero_original() {
+        let mut tablero = Tablero::new(2);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+            ],
+            vec![Objeto::Vacio, Objeto::Vacio],
+        ];
+
+        assert!(tablero.es_resoluble());
+        assert_eq!(
+            tablero.cuadricula[0][0],
+            Objeto::Bomba(false, 1, Direcciones::ORTOGONALES)
+        );
+        assert_eq!(tablero.cantidad_enemigos(), 1);
+    }
+
+    #[test]
+    fn test_limite_de_simulaciones() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+                Objeto::Vacio,
+            ],
+            vec![Objeto::Pared, Objeto::Pared, Objeto::Pared],
+            vec![
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Vacio,
+            ],
+        ];
+
+        // Descartar que alcance con una sola bomba lleva dos simulaciones.
+        assert_eq!(tablero.resolucion_con_limite(1), Resolucion::Desconocida);
+        assert_eq!(tablero.resolucion_con_limite(2), Resolucion::Resoluble(2));
+        assert_eq!(tablero.resolucion(), Resolucion::Resoluble(2));
+    }
+
+    #[test]
+    fn test_tablero_grande_termina() {
+        // 49 bombas en una grilla de 14x14: cada una alcanza a sus vecinas, así que todas están
+        // en la misma cadena y la búsqueda se reduce a una sola candidata.
+        let mut tablero = Tablero::new(14);
+        tablero.cuadricula = (0..14)
+            .map(|y| {
+                (0..14)
+                    .map(|x| match (x % 2, y % 2) {
+                        (0, 0) => Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                        (1, 1) => Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+                        _ => Objeto::Vacio,
+                    })
+                    .collect()
+            })
+            .collect();
+
+        assert_eq!(tablero.posiciones_bombas().len(), 49);
+        assert_eq!(tablero.resolucion(), Resolucion::Irresoluble);
+
+        // Sin los enemigos de las diagonales, cualquier bomba elimina a todos los demás.
+        for fila in tablero.cuadricula.iter_mut() {
+            for objeto in fila.iter_mut() {
+                if matches!(objeto, Objeto::Enemigo(_, _, _)) {
+                    *objeto = Objeto::Vacio;
+                }
+            }
+        }
+        tablero.cuadricula[0][1] = Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun);
+        assert_eq!(tablero.resolucion(), Resolucion::Resoluble(1));
+    }
+
+    #[test]
+    fn test_interruptor_que_cierra_el_camino() {
+        let mut tablero = Tablero::new(4);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Interruptor(1),
+                Objeto::Vacio,
+                Objeto::Vacio,
+            ],
+            vec![Objeto::Vacio, Objeto::Vacio, Objeto::Vacio, Objeto::Vacio],
+            vec![Objeto::Pared, Objeto::Vacio, Objeto::Vacio, Objeto::Vacio],
+            vec![
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                Objeto::Compuerta(1, true),
+                Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+                Objeto::Vacio,
+            ],
+        ];
+
+        // Detonar todas las bombas cierra la compuerta antes de que pase el rayo de (0, 3),
+        // pero detonar solo esa bomba elimina al enemigo.
+        assert_eq!(tablero.resolucion(), Resolucion::Resoluble(1));
+        assert!(tablero.requiere_detonaciones(1));
+        assert_eq!(tablero.resolucion_con_limite(1), Resolucion::Desconocida);
+
+        tablero.cuadricula[3][3] = Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun);
+        assert_eq!(tablero.resolucion(), Resolucion::Irresoluble);
+    }
+
+    #[test]
+    fn test_siguiente_combinacion() {
+        let mut indices = vec![0, 1];
+        let mut combinaciones = vec![indices.clone()];
+        while siguiente_combinacion(&mut indices, 4) {
+            combinaciones.push(indices.clone());
+        }
+        assert_eq!(
+            combinaciones,
+            vec![
+                vec![0, 1],
+                vec![0, 2],
+                vec![0, 3],
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 3]
+            ]
+        );
+    }
+}
@@ -0,0 +1,476 @@
+use super::model::direccion::Direccion;
+use super::model::objeto::Objeto;
+use super::Tablero;
+use std::collections::HashSet;
+
+/// Enumeración que representa las operaciones que se pueden aplicar a la forma de un tablero.
+#[derive(PartialEq, Debug, Clone)]
+pub enum Transformacion {
+    /// Gira el tablero en sentido horario los grados indicados (90, 180 o 270).
+    Rotar(u32),
+    /// Refleja el tablero respecto de un eje vertical (izquierda y derecha se intercambian).
+    EspejarHorizontal,
+    /// Refleja el tablero respecto de un eje horizontal (arriba y abajo se intercambian).
+    EspejarVertical,
+    /// Conserva solo el rectángulo de `ancho` por `alto` celdas que empieza en `(x, y)`.
+    Recortar {
+        x: usize,
+        y: usize,
+        ancho: usize,
+        alto: usize,
+    },
+    /// Agrega `margen` celdas del objeto `relleno` alrededor del tablero.
+    Rellenar { margen: usize, relleno: Objeto },
+}
+
+impl Tablero {
+    /// Devuelve el ancho y el alto de la cuadrícula. Si las filas tienen distinto largo, el
+    /// ancho es el de la fila más larga.
+    pub fn dimensiones(&self) -> (usize, usize) {
+        let ancho = self.cuadricula.iter().map(Vec::len).max().unwrap_or(0);
+        (ancho, self.cuadricula.len())
+    }
+
+    /// Aplica una transformación y devuelve el tablero resultante.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve el tablero transformado, o `Err(String)` si los parámetros no son válidos
+    /// para este tablero.
+    pub fn transformar(&self, transformacion: &Transformacion) -> Result<Tablero, String> {
+        match transformacion {
+            Transformacion::Rotar(grados) => self.rotar(*grados),
+            Transformacion::EspejarHorizontal => Ok(self.espejar_horizontal()),
+            Transformacion::EspejarVertical => Ok(self.espejar_vertical()),
+            Transformacion::Recortar { x, y, ancho, alto } => self.recortar(*x, *y, *ancho, *alto),
+            Transformacion::Rellenar { margen, relleno } => self.rellenar(*margen, relleno.clone()),
+        }
+    }
+
+    /// Devuelve una copia del tablero girada en sentido horario. Los desvíos giran junto con
+    /// el tablero.
+    ///
+    /// # Argumentos
+    ///
+    /// * `grados`: 90, 180 o 270.
+    ///
+    pub fn rotar(&self, grados: u32) -> Result<Tablero, String> {
+        let giros = match grados {
+            90 => 1,
+            180 => 2,
+            270 => 3,
+            _ => {
+                return Err(format!(
+                    "ERROR: Solo se puede rotar 90, 180 o 270 grados, no {}.",
+                    grados
+                ))
+            }
+        };
+        let mut rotado = self.clone();
+        for _ in 0..giros {
+            rotado = rotado.rotado_horario();
+        }
+        Ok(rotado)
+    }
+
+    /// Devuelve una copia del tablero reflejada respecto de un eje vertical.
+    pub fn espejar_horizontal(&self) -> Tablero {
+        let (ancho, alto) = self.dimensiones();
+        self.transformado(
+            (ancho, alto),
+            Objeto::Vacio,
+            |(x, y)| (ancho as i32 - 1 - x, y),
+            Direccion::espejada_horizontal,
+        )
+    }
+
+    /// Devuelve una copia del tablero reflejada respecto de un eje horizontal.
+    pub fn espejar_vertical(&self) -> Tablero {
+        let (ancho, alto) = self.dimensiones();
+        self.transformado(
+            (ancho, alto),
+            Objeto::Vacio,
+            |(x, y)| (x, alto as i32 - 1 - y),
+            Direccion::espejada_vertical,
+        )
+    }
+
+    /// Devuelve el rectángulo de `ancho` por `alto` celdas que empieza en `(x, y)`. Los
+    /// enemigos olvidan las bombas que los golpearon si esas bombas quedan afuera.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve `Err(String)` si el rectángulo está vacío, no entra en el tablero o no es
+    /// cuadrado: los tableros deben ser cuadrados para poder detonarlos.
+    pub fn recortar(
+        &self,
+        x: usize,
+        y: usize,
+        ancho: usize,
+        alto: usize,
+    ) -> Result<Tablero, String> {
+        let (ancho_actual, alto_actual) = self.dimensiones();
+        if ancho == 0 || alto == 0 || x + ancho > ancho_actual || y + alto > alto_actual {
+            return Err(format!(
+                "ERROR: El recorte de {}x{} en ({}, {}) no entra en el tablero de {}x{}.",
+                ancho, alto, x, y, ancho_actual, alto_actual
+            ));
+        }
+        if ancho != alto {
+            return Err(format!(
+                "ERROR: El recorte debe ser cuadrado, pero es de {}x{}.",
+                ancho, alto
+            ));
+        }
+        let (x, y) = (x as i32, y as i32);
+        Ok(self.transformado(
+            (ancho, alto),
+            Objeto::Vacio,
+            |(columna, fila)| (columna - x, fila - y),
+            Direccion::clone,
+        ))
+    }
+
+    /// Devuelve una copia del tablero con `margen` celdas de `relleno` en cada borde.
+    ///
+    /// # Devuelve
+    ///
+    /// Devuelve `Err(String)` si `relleno` no es `Objeto::Vacio` ni `Objeto::Pared`.
+    pub fn rellenar(&self, margen: usize, relleno: Objeto) -> Result<Tablero, String> {
+        if relleno != Objeto::Vacio && relleno != Objeto::Pared {
+            return Err("ERROR: Solo se puede rellenar con vacíos o paredes.".to_string());
+        }
+        let (ancho, alto) = self.dimensiones();
+        let desplazamiento = margen as i32;
+        Ok(self.transformado(
+            (ancho + 2 * margen, alto + 2 * margen),
+            relleno,
+            |(x, y)| (x + desplazamiento, y + desplazamiento),
+            Direccion::clone,
+        ))
+    }
+
+    /// Devuelve las ocho simetrías del tablero: las cuatro rotaciones y las cuatro rotaciones
+    /// del tablero reflejado. La primera es el tablero sin cambios.
+    pub(super) fn simetrias(&self) -> Vec<Tablero> {
+        let mut simetrias = Vec::with_capacity(8);
+        for inicial in [self.clone(), self.espejar_horizontal()] {
+            let mut actual = inicial;
+            for _ in 0..4 {
+                let siguiente = actual.rotado_horario();
+                simetrias.push(actual);
+                actual = siguiente;
+            }
+        }
+        simetrias
+    }
+
+    fn rotado_horario(&self) -> Tablero {
+        let (ancho, alto) = self.dimensiones();
+        self.transformado(
+            (alto, ancho),
+            Objeto::Vacio,
+            |(x, y)| (alto as i32 - 1 - y, x),
+            Direccion::rotada_horario,
+        )
+    }
+
+    /// Construye un tablero moviendo cada celda, con su suelo, a su nueva posición. Las
+    /// direcciones de los desvíos y de las bombas se transforman igual que las celdas. Lo mismo
+    /// pasa con las posiciones de las bombas que golpearon a cada enemigo. Las celdas que caen
+    /// fuera del tablero nuevo se descartan.
+    ///
+    /// # Argumentos
+    ///
+    /// * `(ancho, alto)`: Las dimensiones del tablero nuevo.
+    /// * `fondo`: El objeto de las celdas a las que no llega ninguna celda del tablero actual.
+    /// * `posicion`: Calcula la posición nueva de una celda a partir de la actual.
+    /// * `direccion`: Calcula la dirección nueva de un desvío.
+    ///
+    fn transformado(
+        &self,
+        (ancho, alto): (usize, usize),
+        fondo: Objeto,
+        posicion: impl Fn((i32, i32)) -> (i32, i32),
+        direccion: impl Fn(&Direccion) -> Direccion,
+    ) -> Tablero {
+        let dentro =
+            |(x, y): (i32, i32)| x >= 0 && y >= 0 && (x as usize) < ancho && (y as usize) < alto;
+        let mut tablero = Tablero::new(ancho as i32);
+        tablero.toroidal = self.toroidal;
+        tablero.cuadricula = vec![vec![fondo; ancho]; alto];
+
+        if !self.suelo.is_empty() {
+            tablero.suelo = vec![vec![Objeto::Vacio; ancho]; alto];
+        }
+
+        let transformar = |objeto: &Objeto| match objeto {
+            Objeto::Desvio(actual, enlace) => Objeto::Desvio(direccion(actual), *enlace),
+            Objeto::Espejo(espejo) => Objeto::Espejo(espejo.transformado(&direccion)),
+            Objeto::Bomba(traspaso, alcance, direcciones) => {
+                Objeto::Bomba(*traspaso, *alcance, direcciones.mapear(&direccion))
+            }
+            Objeto::Enemigo(vida, afectadas, tipo) => {
+                let afectadas: HashSet<(i32, i32)> = afectadas
+                    .iter()
+                    .map(|bomba| posicion(*bomba))
+                    .filter(|bomba| dentro(*bomba))
+                    .collect();
+                Objeto::Enemigo(*vida, afectadas, *tipo)
+            }
+            otro => otro.clone(),
+        };
+        for (y, fila) in self.cuadricula.iter().enumerate() {
+            for (x, objeto) in fila.iter().enumerate() {
+                let (nuevo_x, nuevo_y) = posicion((x as i32, y as i32));
+                if !dentro((nuevo_x, nuevo_y)) {
+                    continue;
+                }
+                let (nuevo_x, nuevo_y) = (nuevo_x as usize, nuevo_y as usize);
+                tablero.cuadricula[nuevo_y][nuevo_x] = transformar(objeto);
+                if !tablero.suelo.is_empty() {
+                    tablero.suelo[nuevo_y][nuevo_x] = transformar(self.suelo_en(x, y));
+                }
+            }
+        }
+        tablero
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tablero::leer_tablero;
+    use crate::tablero::model::direccion::Direcciones;
+    use crate::tablero::model::enemigo::TipoDeEnemigo;
+
+    #[test]
+    fn test_rotar_90() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Desvio(Direccion::Derecha, None),
+                Objeto::Roca,
+            ],
+            vec![
+                Objeto::Pared,
+                Objeto::Enemigo(1, [(0, 0)].into_iter().collect(), TipoDeEnemigo::Comun),
+                Objeto::Vacio,
+            ],
+        ];
+        let rotado = tablero.rotar(90);
+        let Ok(rotado) = rotado else {
+            panic!("No se pudo rotar el tablero");
+        };
+        assert_eq!(rotado.dimensiones(), (2, 3));
+        assert_eq!(rotado.tamaño, 2);
+        assert_eq!(
+            rotado.cuadricula,
+            vec![
+                vec![
+                    Objeto::Pared,
+                    Objeto::Bomba(false, 1, Direcciones::ORTOGONALES)
+                ],
+                vec![
+                    Objeto::Enemigo(1, [(1, 0)].into_iter().collect(), TipoDeEnemigo::Comun),
+                    Objeto::Desvio(Direccion::Abajo, None),
+                ],
+                vec![Objeto::Vacio, Objeto::Roca],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rotar_180_equivale_a_espejar_en_ambos_ejes() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Desvio(Direccion::Derecha, None),
+                Objeto::Roca,
+            ],
+            vec![
+                Objeto::Pared,
+                Objeto::Enemigo(1, [(0, 0)].into_iter().collect(), TipoDeEnemigo::Comun),
+                Objeto::Vacio,
+            ],
+        ];
+        assert_eq!(
+            tablero.rotar(180),
+            Ok(tablero.espejar_horizontal().espejar_vertical())
+        );
+        assert!(tablero.rotar(45).is_err());
+    }
+
+    #[test]
+    fn test_espejar_horizontal() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Desvio(Direccion::Derecha, None),
+                Objeto::Roca,
+            ],
+            vec![
+                Objeto::Pared,
+                Objeto::Enemigo(1, [(0, 0)].into_iter().collect(), TipoDeEnemigo::Comun),
+                Objeto::Vacio,
+            ],
+        ];
+        let espejado = tablero.espejar_horizontal();
+        assert_eq!(
+            espejado.cuadricula[0],
+            vec![
+                Objeto::Roca,
+                Objeto::Desvio(Direccion::Izquierda, None),
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+            ]
+        );
+        assert_eq!(
+            espejado.cuadricula[1][1],
+            Objeto::Enemigo(1, [(2, 0)].into_iter().collect(), TipoDeEnemigo::Comun)
+        );
+    }
+
+    #[test]
+    fn test_espejar_vertical() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Desvio(Direccion::Derecha, None),
+                Objeto::Roca,
+            ],
+            vec![
+                Objeto::Pared,
+                Objeto::Enemigo(1, [(0, 0)].into_iter().collect(), TipoDeEnemigo::Comun),
+                Objeto::Vacio,
+            ],
+        ];
+        let espejado = tablero.espejar_vertical();
+        assert_eq!(espejado.cuadricula[0][0], Objeto::Pared);
+        assert_eq!(
+            espejado.cuadricula[0][1],
+            Objeto::Enemigo(1, [(0, 1)].into_iter().collect(), TipoDeEnemigo::Comun)
+        );
+        assert_eq!(
+            espejado.cuadricula[1][1],
+            Objeto::Desvio(Direccion::Derecha, None)
+        );
+    }
+
+    #[test]
+    fn test_recortar() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Desvio(Direccion::Derecha, None),
+                Objeto::Roca,
+            ],
+            vec![
+                Objeto::Pared,
+                Objeto::Enemigo(1, [(0, 0)].into_iter().collect(), TipoDeEnemigo::Comun),
+                Objeto::Vacio,
+            ],
+        ];
+        assert_eq!(
+            tablero.recortar(1, 0, 2, 2).map(|t| t.cuadricula),
+            Ok(vec![
+                vec![Objeto::Desvio(Direccion::Derecha, None), Objeto::Roca],
+                vec![
+                    Objeto::Enemigo(1, HashSet::new(), TipoDeEnemigo::Comun),
+                    Objeto::Vacio
+                ],
+            ])
+        );
+        assert!(tablero.recortar(2, 0, 2, 1).is_err());
+        assert!(tablero.recortar(0, 0, 0, 1).is_err());
+        assert_eq!(
+            tablero.recortar(0, 0, 3, 1).map(|t| t.cuadricula),
+            Err("ERROR: El recorte debe ser cuadrado, pero es de 3x1.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_detonar_tablero_recortado() {
+        let tablero = leer_tablero("B1 F1 _\nW F1 _\n_ _ B2".as_bytes()).ok();
+        let Some(recortado) = tablero.and_then(|t| t.recortar(0, 0, 2, 2).ok()) else {
+            panic!("No se pudo recortar el tablero");
+        };
+        assert_eq!(recortado.tamaño, 2);
+        assert_eq!(recortado.estadisticas().enemigos, 2);
+        assert_eq!(recortado.mapa_de_calor().cantidad(1, 0), 1);
+
+        let mut detonado = recortado.clone();
+        assert!(detonado.detonar(0, 0).is_ok());
+        assert_eq!(detonado.cantidad_enemigos(), 1);
+    }
+
+    #[test]
+    fn test_rellenar() {
+        let mut tablero = Tablero::new(1);
+        tablero.cuadricula = vec![vec![Objeto::Enemigo(
+            1,
+            [(0, 0)].into_iter().collect(),
+            TipoDeEnemigo::Comun,
+        )]];
+
+        let relleno = tablero.rellenar(1, Objeto::Pared);
+        assert_eq!(
+            relleno.as_ref().map(|t| t.cuadricula[1].clone()),
+            Ok(vec![
+                Objeto::Pared,
+                Objeto::Enemigo(1, [(1, 1)].into_iter().collect(), TipoDeEnemigo::Comun),
+                Objeto::Pared,
+            ])
+        );
+        assert_eq!(
+            relleno.map(|t| (t.dimensiones(), t.tamaño)),
+            Ok(((3, 3), 3))
+        );
+        assert!(tablero.rellenar(1, Objeto::Roca).is_err());
+    }
+
+    #[test]
+    fn test_transformar_y_simetrias() {
+        let mut tablero = Tablero::new(3);
+        tablero.cuadricula = vec![
+            vec![
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+                Objeto::Desvio(Direccion::Derecha, None),
+                Objeto::Roca,
+            ],
+            vec![
+                Objeto::Pared,
+                Objeto::Enemigo(1, [(0, 0)].into_iter().collect(), TipoDeEnemigo::Comun),
+                Objeto::Vacio,
+            ],
+        ];
+        assert_eq!(
+            tablero.transformar(&Transformacion::EspejarVertical),
+            Ok(tablero.espejar_vertical())
+        );
+        let simetrias = tablero.simetrias();
+        assert_eq!(simetrias.len(), 8);
+        assert_eq!(simetrias[0], tablero);
+        assert_eq!(simetrias[3].rotado_horario(), tablero);
+    }
+
+    #[test]
+    fn test_el_suelo_gira_con_el_tablero() {
+        let tablero = leer_tablero("B1+DR _\n_ _\n".as_bytes()).unwrap();
+        let rotado = tablero.rotar(90).unwrap();
+        assert_eq!(
+            rotado.cuadricula[0][1],
+            Objeto::Bomba(false, 1, Direcciones::ORTOGONALES)
+        );
+        assert_eq!(
+            *rotado.suelo_en(1, 0),
+            Objeto::Desvio(Direccion::Abajo, None)
+        );
+        assert_eq!(rotado.rotar(270), Ok(tablero.clone()));
+        assert_eq!(tablero.rellenar(1, Objeto::Pared).unwrap().suelo.len(), 4);
+    }
+}
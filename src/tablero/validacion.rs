@@ -0,0 +1,494 @@
+use super::model::direccion::Direccion;
+use super::model::objeto::{convertir_celda, Objeto};
+use super::resolucion::Resolucion;
+use super::{Tablero, DIRECTIVA_TOROIDAL};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::fs;
+use std::io;
+
+/// Enumeración que representa qué tan grave es un problema encontrado en un tablero.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Gravedad {
+    /// El tablero no se puede cargar o usar tal como está.
+    Error,
+    /// El tablero se puede usar, pero probablemente tiene un defecto de diseño.
+    Advertencia,
+}
+
+/// Representa un problema encontrado al validar un tablero.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Problema {
+    pub gravedad: Gravedad,
+    /// Posición `(x, y)` de la celda afectada, si el problema corresponde a una celda.
+    pub posicion: Option<(i32, i32)>,
+    pub descripcion: String,
+}
+
+impl Problema {
+    fn error(posicion: Option<(i32, i32)>, descripcion: String) -> Problema {
+        Problema {
+            gravedad: Gravedad::Error,
+            posicion,
+            descripcion,
+        }
+    }
+
+    fn advertencia(posicion: Option<(i32, i32)>, descripcion: String) -> Problema {
+        Problema {
+            gravedad: Gravedad::Advertencia,
+            posicion,
+            descripcion,
+        }
+    }
+}
+
+impl fmt::Display for Problema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gravedad = match self.gravedad {
+            Gravedad::Error => "ERROR",
+            Gravedad::Advertencia => "ADVERTENCIA",
+        };
+        match self.posicion {
+            Some((x, y)) => write!(f, "{} en ({}, {}): {}", gravedad, x, y, self.descripcion),
+            None => write!(f, "{}: {}", gravedad, self.descripcion),
+        }
+    }
+}
+
+/// Valida un archivo de tablero y devuelve todos los problemas encontrados, sin detenerse en el
+/// primero.
+///
+/// # Argumentos
+///
+/// * `input_file`: La ubicación del archivo de tablero.
+///
+/// # Devoluciones
+///
+/// Devuelve la lista de problemas (vacía si el tablero es correcto) o un error de E/S si no se
+/// pudo leer el archivo.
+///
+pub fn validar_archivo(input_file: &str) -> Result<Vec<Problema>, io::Error> {
+    let contenido = fs::read_to_string(input_file)?;
+    Ok(validar_contenido(&contenido))
+}
+
+/// Valida el contenido de un tablero, con el formato de los archivos de entrada.
+///
+/// Primero revisa cada símbolo y la forma de la cuadrícula; si no hay errores, revisa además el
+/// diseño del tablero: portales sin pareja, interruptores y objetos enlazados sin su
+/// contraparte, bombas con alcance mayor al tablero, desvíos que apuntan a una pared o
+/// fuera del tablero, desvíos que forman ciclos y enemigos que no se pueden eliminar.
+pub fn validar_contenido(contenido: &str) -> Vec<Problema> {
+    let mut problemas = Vec::new();
+    let mut cuadricula = Vec::new();
+    let mut suelo = Vec::new();
+
+    let toroidal = contenido.lines().next().map(str::trim) == Some(DIRECTIVA_TOROIDAL);
+    for (y, linea) in contenido.lines().skip(usize::from(toroidal)).enumerate() {
+        let mut fila = Vec::new();
+        let mut fila_de_suelo = Vec::new();
+        for (x, simbolo) in linea.split_whitespace().enumerate() {
+            match convertir_celda(simbolo) {
+                Ok((ocupante, debajo)) => {
+                    fila.push(ocupante);
+                    fila_de_suelo.push(debajo);
+                }
+                Err(err) => problemas.push(Problema::error(
+                    Some((x as i32, y as i32)),
+                    format!("'{}': {}", simbolo, err),
+                )),
+            }
+        }
+        cuadricula.push((fila, linea.split_whitespace().count()));
+        suelo.push(fila_de_suelo);
+    }
+
+    problemas.extend(validar_forma(&cuadricula));
+    if !problemas.is_empty() {
+        return problemas;
+    }
+
+    let mut tablero = Tablero::new(cuadricula.len() as i32);
+    tablero.cuadricula = cuadricula.into_iter().map(|(fila, _)| fila).collect();
+    tablero.toroidal = toroidal;
+    if suelo
+        .iter()
+        .flatten()
+        .any(|objeto| *objeto != Objeto::Vacio)
+    {
+        tablero.suelo = suelo;
+    }
+    problemas.extend(validar_portales(&tablero));
+    problemas.extend(validar_enlaces(&tablero));
+    problemas.extend(validar_alcances(&tablero));
+    problemas.extend(validar_desvios(&tablero));
+    problemas.extend(validar_ciclos_de_desvios(&tablero));
+    problemas.extend(validar_enemigos(&tablero));
+    problemas
+}
+
+fn validar_forma(cuadricula: &[(Vec<Objeto>, usize)]) -> Vec<Problema> {
+    let mut problemas = Vec::new();
+    let Some((_, columnas)) = cuadricula.first() else {
+        problemas.push(Problema::error(None, "El tablero está vacío".to_string()));
+        return problemas;
+    };
+
+    for (y, (_, cantidad)) in cuadricula.iter().enumerate() {
+        if cantidad != columnas {
+            problemas.push(Problema::error(
+                None,
+                format!(
+                    "La fila {} tiene {} columnas, se esperaban {}",
+                    y, cantidad, columnas
+                ),
+            ));
+        }
+    }
+    if cuadricula.len() != *columnas {
+        problemas.push(Problema::error(
+            None,
+            format!(
+                "El tablero debe ser cuadrado, pero tiene {} filas y {} columnas",
+                cuadricula.len(),
+                columnas
+            ),
+        ));
+    }
+    problemas
+}
+
+fn validar_alcances(tablero: &Tablero) -> Vec<Problema> {
+    let mut problemas = Vec::new();
+    for (x, y) in tablero.posiciones_bombas() {
+        if let Objeto::Bomba(_, alcance, _) | Objeto::BombaDeArea(_, alcance, _) =
+            tablero.cuadricula[y as usize][x as usize]
+        {
+            if alcance >= tablero.tamaño {
+                problemas.push(Problema::advertencia(
+                    Some((x, y)),
+                    format!(
+                        "La bomba tiene alcance {}, mayor que el tablero de tamaño {}",
+                        alcance, tablero.tamaño
+                    ),
+                ));
+            }
+        }
+    }
+    problemas
+}
+
+fn validar_portales(tablero: &Tablero) -> Vec<Problema> {
+    tablero
+        .portales_sin_pareja()
+        .into_iter()
+        .map(|(posicion, descripcion)| Problema::error(Some(posicion), descripcion))
+        .collect()
+}
+
+fn validar_enlaces(tablero: &Tablero) -> Vec<Problema> {
+    let interruptores = tablero.interruptores();
+    let enlazados = tablero.enlazados();
+    let mut problemas = Vec::new();
+    for (id, posiciones) in &enlazados {
+        if !interruptores.contains(id) {
+            problemas.push(Problema::advertencia(
+                posiciones.first().copied(),
+                format!("Ningún interruptor I{} controla este objeto", id),
+            ));
+        }
+    }
+    for id in interruptores.difference(&enlazados.keys().copied().collect()) {
+        problemas.push(Problema::advertencia(
+            None,
+            format!(
+                "El interruptor I{} no controla ninguna compuerta ni desvío",
+                id
+            ),
+        ));
+    }
+    problemas
+}
+
+fn validar_desvios(tablero: &Tablero) -> Vec<Problema> {
+    let mut problemas = Vec::new();
+    for ((x, y), direccion) in desvios(tablero) {
+        let siguiente = tablero
+            .calcular_nueva_posicion(x, y, &direccion, 1)
+            .and_then(|(siguiente_x, siguiente_y)| {
+                tablero.obtener_objeto_en_posicion(siguiente_x, siguiente_y)
+            });
+        let descripcion = match siguiente {
+            None => "El desvío apunta fuera del tablero",
+            Some(Objeto::Pared) => "El desvío apunta directamente a una pared",
+            Some(_) => continue,
+        };
+        problemas.push(Problema::advertencia(
+            Some((x as i32, y as i32)),
+            descripcion.to_string(),
+        ));
+    }
+    problemas
+}
+
+fn validar_ciclos_de_desvios(tablero: &Tablero) -> Vec<Problema> {
+    let siguientes: HashMap<(usize, usize), (usize, usize)> = desvios(tablero)
+        .into_iter()
+        .filter_map(|(posicion, direccion)| {
+            siguiente_desvio(tablero, posicion, direccion).map(|siguiente| (posicion, siguiente))
+        })
+        .collect();
+
+    let mut ciclos: BTreeSet<Vec<(usize, usize)>> = BTreeSet::new();
+    for &inicio in siguientes.keys() {
+        let mut recorrido = vec![inicio];
+        let mut actual = inicio;
+        while let Some(&siguiente) = siguientes.get(&actual) {
+            if let Some(indice) = recorrido.iter().position(|&p| p == siguiente) {
+                let mut ciclo = recorrido[indice..].to_vec();
+                ciclo.sort();
+                ciclos.insert(ciclo);
+                break;
+            }
+            recorrido.push(siguiente);
+            actual = siguiente;
+        }
+    }
+
+    ciclos
+        .into_iter()
+        .map(|ciclo| {
+            let posiciones: Vec<String> = ciclo
+                .iter()
+                .map(|(x, y)| format!("({}, {})", x, y))
+                .collect();
+            Problema::advertencia(
+                None,
+                format!("Los desvíos {} forman un ciclo", posiciones.join(", ")),
+            )
+        })
+        .collect()
+}
+
+/// Advierte sobre los enemigos que no se pueden eliminar. Sin interruptores alcanza con
+/// detonar todas las bombas; con interruptores el orden importa, así que se usa la búsqueda de
+/// `Tablero::resolucion` y solo se advierte si se sabe que el tablero no es resoluble.
+fn validar_enemigos(tablero: &Tablero) -> Vec<Problema> {
+    if !tablero.interruptores().is_empty() {
+        return match tablero.resolucion() {
+            Resolucion::Irresoluble => vec![Problema::advertencia(
+                None,
+                "Ninguna secuencia de detonaciones elimina a todos los enemigos".to_string(),
+            )],
+            Resolucion::Resoluble(_) | Resolucion::Desconocida => Vec::new(),
+        };
+    }
+    let mut simulacion = tablero.clone();
+    for (x, y) in tablero.posiciones_bombas() {
+        let _ya_detonada = simulacion.detonar(x, y);
+    }
+
+    let mut problemas = Vec::new();
+    for (y, fila) in simulacion.cuadricula.iter().enumerate() {
+        for (x, objeto) in fila.iter().enumerate() {
+            if let Objeto::Enemigo(vida, _, _) = objeto {
+                problemas.push(Problema::advertencia(
+                    Some((x as i32, y as i32)),
+                    format!(
+                        "El enemigo no puede ser eliminado: aún detonando todas las bombas le queda vida {}",
+                        vida
+                    ),
+                ));
+            }
+        }
+    }
+    problemas
+}
+
+/// Devuelve las posiciones y direcciones de los desvíos, incluidos los que están en el suelo.
+fn desvios(tablero: &Tablero) -> Vec<((usize, usize), Direccion)> {
+    tablero
+        .objetos_con_suelo()
+        .into_iter()
+        .filter_map(|(posicion, objeto)| match objeto {
+            Objeto::Desvio(direccion, _) => Some((posicion, direccion.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Sigue el rayo que sale de un desvío hasta el próximo desvío, ignorando las rocas (que una
+/// bomba de traspaso atraviesa) y deteniéndose en las paredes y en el borde del tablero. En un
+/// tablero toroidal el rayo da la vuelta y, si no encuentra otro desvío, vuelve al de partida.
+fn siguiente_desvio(
+    tablero: &Tablero,
+    (x, y): (usize, usize),
+    direccion: Direccion,
+) -> Option<(usize, usize)> {
+    let mut paso = 1;
+    loop {
+        let (nuevo_x, nuevo_y) = tablero.calcular_nueva_posicion(x, y, &direccion, paso)?;
+        if let Some(Objeto::Desvio(_, _)) = tablero.baldosa_en(nuevo_x, nuevo_y) {
+            return Some((nuevo_x, nuevo_y));
+        }
+        match tablero.obtener_objeto_en_posicion(nuevo_x, nuevo_y)? {
+            Objeto::Pared => return None,
+            _ => paso += 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descripciones(problemas: &[Problema]) -> Vec<String> {
+        problemas.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_tablero_valido() {
+        assert!(validar_contenido("B2 _ F1\nW _ _\n_ _ _").is_empty());
+    }
+
+    #[test]
+    fn test_informa_todos_los_simbolos_invalidos() {
+        let problemas = validar_contenido("B1 X F3\n_ B0 _\n_ _ DQ");
+        assert_eq!(
+            descripciones(&problemas),
+            vec![
+                "ERROR en (1, 0): 'X': Símbolo no válido en el laberinto",
+                "ERROR en (2, 0): 'F3': Valor de vida de enemigo no válido",
+                "ERROR en (1, 1): 'B0': Valor de alcance de bomba no válido",
+                "ERROR en (2, 2): 'DQ': Dirección de desvío no válida",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filas_irregulares() {
+        let problemas = validar_contenido("_ _ _\n_ _\n_ _ _ _");
+        assert_eq!(
+            descripciones(&problemas),
+            vec![
+                "ERROR: La fila 1 tiene 2 columnas, se esperaban 3",
+                "ERROR: La fila 2 tiene 4 columnas, se esperaban 3",
+            ]
+        );
+
+        let problemas = validar_contenido("_ _ _\n_ _ _");
+        assert_eq!(
+            descripciones(&problemas),
+            vec!["ERROR: El tablero debe ser cuadrado, pero tiene 2 filas y 3 columnas"]
+        );
+        assert_eq!(
+            descripciones(&validar_contenido("")),
+            vec!["ERROR: El tablero está vacío"]
+        );
+    }
+
+    #[test]
+    fn test_portales_sin_pareja() {
+        let problemas = validar_contenido("P1 _ P2\n_ P2 _\nP3 P2 _");
+        assert_eq!(
+            descripciones(&problemas),
+            vec![
+                "ERROR en (0, 0): El portal P1 no tiene pareja",
+                "ERROR en (2, 0): El portal P2 aparece 3 veces, pero debe formar una sola pareja",
+                "ERROR en (0, 2): El portal P3 no tiene pareja",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enlaces_sin_contraparte() {
+        let problemas = validar_contenido("I1 C1 _\nI2 _ DU:3\n_ _ _");
+        assert_eq!(
+            descripciones(&problemas),
+            vec![
+                "ADVERTENCIA en (2, 1): Ningún interruptor I3 controla este objeto",
+                "ADVERTENCIA: El interruptor I2 no controla ninguna compuerta ni desvío",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tablero_toroidal() {
+        // En un tablero toroidal el desvío del borde apunta al otro lado, no hacia afuera.
+        assert_eq!(
+            descripciones(&validar_contenido("_ W DR\n_ B1 _\n_ _ _")),
+            vec!["ADVERTENCIA en (2, 0): El desvío apunta fuera del tablero"]
+        );
+        assert!(validar_contenido("@toroidal\n_ W DR\n_ B1 _\n_ _ _").is_empty());
+    }
+
+    #[test]
+    fn test_problemas_de_diseno() {
+        let problemas = validar_contenido("B3 _ DU\n_ W _\nDU _ F2");
+        assert_eq!(
+            descripciones(&problemas),
+            vec![
+                "ADVERTENCIA en (0, 0): La bomba tiene alcance 3, mayor que el tablero de tamaño 3",
+                "ADVERTENCIA en (2, 0): El desvío apunta fuera del tablero",
+                "ADVERTENCIA en (2, 2): El enemigo no puede ser eliminado: aún detonando todas las bombas le queda vida 2",
+            ]
+        );
+        assert!(problemas
+            .iter()
+            .all(|p| p.gravedad == Gravedad::Advertencia));
+    }
+
+    #[test]
+    fn test_enemigos_con_interruptores() {
+        // Detonar todas las bombas cierra la compuerta, pero detonar solo (0, 3) elimina al
+        // enemigo.
+        let contenido = "B1 I1 _ _\n_ _ _ _\nW _ _ _\nB2 C1:A F1 _";
+        assert!(validar_contenido(contenido).is_empty());
+
+        let problemas = validar_contenido("B1 I1 _ _\n_ _ _ _\nW _ _ _\nB2 C1:A F1 F1");
+        assert_eq!(
+            descripciones(&problemas),
+            vec!["ADVERTENCIA: Ninguna secuencia de detonaciones elimina a todos los enemigos"]
+        );
+    }
+
+    #[test]
+    fn test_desvio_hacia_pared() {
+        let problemas = validar_contenido("DR W\n_ _");
+        assert_eq!(
+            descripciones(&problemas),
+            vec!["ADVERTENCIA en (0, 0): El desvío apunta directamente a una pared"]
+        );
+    }
+
+    #[test]
+    fn test_ciclo_de_desvios() {
+        let problemas = validar_contenido("DR R DD\n_ _ _\nDU _ DL");
+        assert_eq!(
+            descripciones(&problemas),
+            vec!["ADVERTENCIA: Los desvíos (0, 0), (0, 2), (2, 0), (2, 2) forman un ciclo"]
+        );
+
+        // Una pared corta el ciclo.
+        let problemas = validar_contenido("DR W DD\n_ _ _\nDU _ DL");
+        assert!(!descripciones(&problemas)
+            .iter()
+            .any(|p| p.contains("forman un ciclo")));
+    }
+
+    #[test]
+    fn test_celdas_con_suelo() {
+        let problemas = validar_contenido("B1+W _\n_ _");
+        assert_eq!(
+            descripciones(&problemas),
+            vec!["ERROR en (0, 0): 'B1+W': Solo desvíos, espejos, divisores, portales e interruptores van en el suelo"]
+        );
+
+        // Los desvíos del suelo también forman ciclos.
+        let problemas = validar_contenido("F1+DR R DD\n_ _ _\nDU _ B1+DL");
+        assert!(descripciones(&problemas)
+            .iter()
+            .any(|p| p.contains("Los desvíos (0, 0), (0, 2), (2, 0), (2, 2) forman un ciclo")));
+    }
+}
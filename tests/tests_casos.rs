@@ -0,0 +1,142 @@
+//! Ejecuta los casos de `tests/casos`. Cada caso es un directorio con:
+//!
+//! * `tablero.txt`: El tablero inicial.
+//! * `detonacion.txt`: Las coordenadas `x y` de la bomba a detonar.
+//! * `esperado.txt`: El tablero esperado luego de la detonación, o el mensaje de error si la
+//!   detonación debe fallar.
+//!
+//! Con la variable de entorno `ACTUALIZAR_ESPERADOS=1` se reescriben los `esperado.txt` con el
+//! resultado actual en lugar de compararlos.
+
+use std::env;
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use tp_individual::tablero::diferencias::diferencias_a_texto;
+use tp_individual::tablero::{crear_tablero, escribir_tablero, leer_tablero};
+
+const VARIABLE_ACTUALIZAR: &str = "ACTUALIZAR_ESPERADOS";
+
+fn directorios_de_casos() -> Vec<PathBuf> {
+    let raiz = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("casos");
+    let entradas = match fs::read_dir(&raiz) {
+        Ok(entradas) => entradas,
+        Err(err) => panic!("No se pudo leer {}: {}", raiz.display(), err),
+    };
+    let mut casos: Vec<PathBuf> = entradas
+        .filter_map(|entrada| entrada.ok().map(|e| e.path()))
+        .filter(|ruta| ruta.is_dir())
+        .collect();
+    casos.sort();
+    casos
+}
+
+fn leer(ruta: &Path) -> Result<String, String> {
+    fs::read_to_string(ruta).map_err(|err| format!("No se pudo leer {}: {}", ruta.display(), err))
+}
+
+fn leer_detonacion(caso: &Path) -> Result<(i32, i32), String> {
+    let contenido = leer(&caso.join("detonacion.txt"))?;
+    let coordenadas: Vec<i32> = contenido
+        .split_whitespace()
+        .map(|valor| valor.parse::<i32>())
+        .collect::<Result<_, _>>()
+        .map_err(|_| format!("Coordenadas inválidas: {:?}", contenido.trim()))?;
+    match coordenadas.as_slice() {
+        [x, y] => Ok((*x, *y)),
+        _ => Err(format!(
+            "Se esperaban dos coordenadas: {:?}",
+            contenido.trim()
+        )),
+    }
+}
+
+/// Ejecuta un caso y devuelve el resultado: el tablero resultante en el formato de los archivos
+/// de tablero, o el mensaje de error de la detonación.
+fn ejecutar_caso(caso: &Path) -> Result<String, String> {
+    let archivo = caso.join("tablero.txt");
+    let mut tablero = crear_tablero(&archivo.to_string_lossy())
+        .map_err(|err| format!("No se pudo cargar {}: {}", archivo.display(), err))?;
+    let (x, y) = leer_detonacion(caso)?;
+    if let Err(error) = tablero.detonar(x, y) {
+        return Ok(format!("{}\n", error));
+    }
+
+    let mut salida = Vec::new();
+    escribir_tablero(&mut salida, &tablero).map_err(|err| err.to_string())?;
+    Ok(String::from_utf8_lossy(&salida).into_owned())
+}
+
+/// Compara el resultado con lo esperado y devuelve una descripción legible de las diferencias.
+fn comparar(obtenido: &str, esperado: &str) -> Option<String> {
+    if obtenido.trim_end() == esperado.trim_end() {
+        return None;
+    }
+    let tableros = (
+        leer_tablero(Cursor::new(esperado)),
+        leer_tablero(Cursor::new(obtenido)),
+    );
+    match tableros {
+        (Ok(esperado), Ok(obtenido)) if !esperado.diferencias(&obtenido).is_empty() => {
+            Some(format!(
+                "celdas distintas (esperado -> obtenido):\n{}\nobtenido:\n{}",
+                diferencias_a_texto(&esperado.diferencias(&obtenido)),
+                esperado.renderizar_diferencias(&obtenido, false)
+            ))
+        }
+        _ => Some(format!(
+            "esperado:\n{}\nobtenido:\n{}",
+            esperado.trim_end(),
+            obtenido.trim_end()
+        )),
+    }
+}
+
+#[test]
+fn test_casos() {
+    let actualizar = env::var(VARIABLE_ACTUALIZAR).is_ok_and(|valor| valor == "1");
+    let casos = directorios_de_casos();
+    assert!(!casos.is_empty(), "No se encontraron casos en tests/casos");
+
+    let mut fallas = Vec::new();
+    for caso in &casos {
+        let nombre = caso.file_name().unwrap_or_default().to_string_lossy();
+        let obtenido = match ejecutar_caso(caso) {
+            Ok(obtenido) => obtenido,
+            Err(error) => {
+                fallas.push(format!("{}: {}", nombre, error));
+                continue;
+            }
+        };
+
+        let archivo_esperado = caso.join("esperado.txt");
+        if actualizar {
+            if let Err(err) = fs::write(&archivo_esperado, &obtenido) {
+                fallas.push(format!("{}: no se pudo actualizar: {}", nombre, err));
+            }
+            continue;
+        }
+        match leer(&archivo_esperado) {
+            Ok(esperado) => {
+                if let Some(diferencia) = comparar(&obtenido, &esperado) {
+                    fallas.push(format!("{}: {}", nombre, diferencia));
+                }
+            }
+            Err(error) => fallas.push(format!(
+                "{}: {} (use {}=1 para generarlo)",
+                nombre, error, VARIABLE_ACTUALIZAR
+            )),
+        }
+    }
+
+    if !fallas.is_empty() {
+        panic!(
+            "Fallaron {} de {} casos:\n\n{}",
+            fallas.len(),
+            casos.len(),
+            fallas.join("\n\n")
+        );
+    }
+}
@@ -2,7 +2,8 @@
 mod integration_tests {
     use std::collections::HashSet;
 
-    use tp_individual::tablero::model::direccion::Direccion;
+    use tp_individual::tablero::model::direccion::{Direccion, Direcciones};
+    use tp_individual::tablero::model::enemigo::TipoDeEnemigo;
     use tp_individual::tablero::model::objeto::Objeto;
     use tp_individual::tablero::{crear_tablero, guardar_tablero, Tablero};
 
@@ -12,12 +13,7 @@ mod integration_tests {
 
         let maze_file = "test_maze1.txt";
         if let Err(err) = std::fs::write(maze_file, maze_content) {
-            assert!(
-                false,
-                "Fallo la creacion del archivo del tablero: {:?}",
-                err
-            );
-            return;
+            panic!("Fallo la creacion del archivo del tablero: {:?}", err);
         }
 
         // Crear el tablero desde el archivo de prueba
@@ -29,21 +25,21 @@ mod integration_tests {
             }
         };
 
-        if let Objeto::Bomba(false, 1) = tablero.cuadricula[0][0] {
+        if let Objeto::Bomba(false, 1, Direcciones::ORTOGONALES) = tablero.cuadricula[0][0] {
             // Detonar la bomba en la posición (0, 0)
-            if let Ok(_) = tablero.detonar(0, 0) {
+            if tablero.detonar(0, 0).is_ok() {
                 // Verificar que la bomba haya sido reemplazada por un espacio vacío después de la detonación
                 if let Objeto::Vacio = tablero.cuadricula[0][0] {
                     assert_eq!(tablero.cuadricula[0][1], Objeto::Roca);
                     assert_eq!(tablero.cuadricula[1][2], Objeto::Pared);
                 } else {
-                    assert!(false, "No pudo detonar la bomba");
+                    panic!("No pudo detonar la bomba");
                 }
             } else {
-                assert!(false, "No pudo detonar la bombas");
+                panic!("No pudo detonar la bombas");
             }
         } else {
-            assert!(false, "Estado inicial de la bomba incorrecto");
+            panic!("Estado inicial de la bomba incorrecto");
         }
     }
 
@@ -53,12 +49,7 @@ mod integration_tests {
 
         let maze_file = "test_maze2.txt";
         if let Err(err) = std::fs::write(maze_file, maze_content) {
-            assert!(
-                false,
-                "Fallo la creacion del archivo del tablero: {:?}",
-                err
-            );
-            return;
+            panic!("Fallo la creacion del archivo del tablero: {:?}", err);
         }
 
         let mut tablero = match crear_tablero(maze_file) {
@@ -70,32 +61,33 @@ mod integration_tests {
         };
 
         // Verificar que la bomba esté en la posición deseada antes de la detonación
-        if let Objeto::Bomba(false, 2) = tablero.cuadricula[0][0] {
-            if let Ok(_) = tablero.detonar(0, 0) {
+        if let Objeto::Bomba(false, 2, Direcciones::ORTOGONALES) = tablero.cuadricula[0][0] {
+            if tablero.detonar(0, 0).is_ok() {
                 if let Objeto::Vacio = tablero.cuadricula[0][0] {
                     assert_eq!(tablero.cuadricula[1][0], Objeto::Pared);
-                    if let Objeto::Desvio(Direccion::Abajo) = tablero.cuadricula[0][1] {
+                    if let Objeto::Desvio(Direccion::Abajo, None) = tablero.cuadricula[0][1] {
                         assert_eq!(tablero.cuadricula[0][2], Objeto::Vacio);
-                        if let Objeto::Enemigo(1, set) = &tablero.cuadricula[1][1] {
+                        if let Objeto::Enemigo(1, set, _) = &tablero.cuadricula[1][1] {
                             assert!(set.contains(&(0, 0)));
                         } else {
-                            assert!(false, "Se esperaba un enemigo en (1, 1)");
+                            panic!("Se esperaba un enemigo en (1, 1)");
                         }
                     } else {
-                        assert!(false, "Se esperaba DD en (0, 1)");
+                        panic!("Se esperaba DD en (0, 1)");
                     }
 
-                    if let Objeto::Bomba(true, 3) = tablero.cuadricula[2][0] {
-                        return;
-                    }
+                    assert_eq!(
+                        tablero.cuadricula[2][0],
+                        Objeto::Bomba(true, 3, Direcciones::ORTOGONALES)
+                    );
                 } else {
-                    assert!(false, "Fallo la detonacion");
+                    panic!("Fallo la detonacion");
                 }
             } else {
-                assert!(false, "Fallo la detonacion");
+                panic!("Fallo la detonacion");
             }
         } else {
-            assert!(false, "Estado inicial de la bomba incorrecto");
+            panic!("Estado inicial de la bomba incorrecto");
         }
     }
 
@@ -104,8 +96,7 @@ mod integration_tests {
         let maze_content = "B2 _ _\n_ _ _\n_ _ _";
         let maze_file = "test_maze3.txt";
         if let Err(err) = std::fs::write(maze_file, maze_content) {
-            assert!(false, "Fallo la creacion del archivo: {:?}", err);
-            return;
+            panic!("Fallo la creacion del archivo: {:?}", err);
         }
 
         let mut tablero = match crear_tablero(maze_file) {
@@ -117,20 +108,18 @@ mod integration_tests {
         };
 
         // Intentar detonar una bomba en (0, 0), lo cual debe ser exitoso
-        if let Ok(_) = tablero.detonar(0, 0) {
+        if tablero.detonar(0, 0).is_ok() {
             // Intentar detonar una bomba en (1, 1), que no es una bomba (debe generar un error)
             if let Err(err) = tablero.detonar(1, 1) {
-                assert!(true, "Se esperaba un error al detonar en (1, 1): {:?}", err);
                 assert_eq!(
                     err,
                     "ERROR: No es una bomba, no se puede detonar.".to_string()
                 );
-                return;
             } else {
-                assert!(false, "Fallo al generar un error en (1, 1)");
+                panic!("Fallo al generar un error en (1, 1)");
             }
         } else {
-            assert!(false, "Fallo la detonacion at (0, 0)");
+            panic!("Fallo la detonacion at (0, 0)");
         }
     }
 
@@ -139,31 +128,33 @@ mod integration_tests {
         let mut tablero = Tablero::new(3);
         tablero.cuadricula = vec![
             vec![
-                Objeto::Bomba(false, 2),
-                Objeto::Desvio(Direccion::Abajo),
+                Objeto::Bomba(false, 2, Direcciones::ORTOGONALES),
+                Objeto::Desvio(Direccion::Abajo, None),
                 Objeto::Vacio,
             ],
             vec![
                 Objeto::Vacio,
-                Objeto::Enemigo(3, HashSet::new()),
+                Objeto::Enemigo(3, HashSet::new(), TipoDeEnemigo::Comun),
                 Objeto::Roca,
             ],
-            vec![Objeto::Pared, Objeto::Vacio, Objeto::Bomba(false, 1)],
+            vec![
+                Objeto::Pared,
+                Objeto::Vacio,
+                Objeto::Bomba(false, 1, Direcciones::ORTOGONALES),
+            ],
         ];
 
         let x = 0;
         let y = 0;
 
         if let Err(err) = tablero.detonar(x, y) {
-            assert!(false, "Fallo al detonar la bomba: {:?}", err);
-            return;
+            panic!("Fallo al detonar la bomba: {:?}", err);
         }
 
         let output_dir = ".";
         let output_file = "test_output.txt";
         if let Err(err) = guardar_tablero(output_dir, &tablero, output_file) {
-            assert!(false, "Fallo el guardado del tablero: {:?}", err);
-            return;
+            panic!("Fallo el guardado del tablero: {:?}", err);
         }
 
         // Leer el contenido del archivo de salida generado por el programa
@@ -179,11 +170,11 @@ mod integration_tests {
 
                 // Limpiar el archivo de salida después de la prueba
                 if let Err(err) = std::fs::remove_file(output_file) {
-                    assert!(false, "Fallo la eliminacion del output: {:?}", err);
+                    panic!("Fallo la eliminacion del output: {:?}", err);
                 }
             }
             Err(err) => {
-                assert!(false, "fallo la lectura del output: {:?}", err);
+                panic!("fallo la lectura del output: {:?}", err);
             }
         }
     }